@@ -0,0 +1,168 @@
+//! Subsets the bundled fonts when the `subset` feature is enabled.
+//!
+//! The retained characters are configured with environment variables, which can be set from the
+//! `[env]` section of the application `.cargo/config.toml`:
+//! - `EGUI_UI_REFRESH_SUBSET_RANGES`: comma separated list of unicode ranges kept in the text
+//!   fonts (Inter and Jetbrains Mono), e.g. `0020-007E,00A0-00FF,20AC`. Defaults to
+//!   Latin scripts and general punctuation (`DEFAULT_RANGES`).
+//! - `EGUI_UI_REFRESH_SUBSET_ICONS`: comma separated list of icons kept in the Phosphor font,
+//!   either by the name of their constant in `src/icons.rs` (`WARNING_CIRCLE` or
+//!   `warning-circle`) or by codepoint (`U+E4F6`). Defaults to every icon declared in
+//!   `src/icons.rs`.
+//!
+//! The subset fonts are written to `OUT_DIR` and included by `src/fonts.rs` in place of the
+//! original files.
+
+fn main() {
+    #[cfg(feature = "subset")]
+    subset::run();
+}
+
+#[cfg(feature = "subset")]
+mod subset {
+    use std::collections::BTreeMap;
+    use std::ops::RangeInclusive;
+    use std::path::Path;
+
+    const RANGES_VAR: &str = "EGUI_UI_REFRESH_SUBSET_RANGES";
+    const ICONS_VAR: &str = "EGUI_UI_REFRESH_SUBSET_ICONS";
+
+    /// Basic Latin, Latin-1 Supplement, Latin Extended-A, General Punctuation, Currency Symbols
+    /// and the replacement character used by egui.
+    const DEFAULT_RANGES: &str = "0020-007E,00A0-00FF,0100-017F,2000-206F,20A0-20CF,25FB";
+
+    /// Text fonts, subset to the configured unicode ranges.
+    const TEXT_FONTS: &[(&str, &str)] = &[
+        ("font_inter_regular", "inter/Inter-Regular.ttf"),
+        ("font_inter_italic", "inter/Inter-Italic.ttf"),
+        ("font_inter_medium", "inter/Inter-Medium.ttf"),
+        ("font_inter_semibold", "inter/Inter-SemiBold.ttf"),
+        ("font_inter_bold", "inter/Inter-Bold.ttf"),
+        ("font_inter_bold_italic", "inter/Inter-BoldItalic.ttf"),
+        ("font_jetbrains_mono", "jetbrainsmono/JetBrainsMonoNL-Regular.ttf"),
+    ];
+
+    /// Icon fonts, subset to the configured icons.
+    const ICON_FONTS: &[(&str, &str)] = &[("font_phosphor", "phosphor/Phosphor.ttf")];
+
+    pub fn run() {
+        println!("cargo:rerun-if-env-changed={RANGES_VAR}");
+        println!("cargo:rerun-if-env-changed={ICONS_VAR}");
+        println!("cargo:rerun-if-changed=src/icons.rs");
+
+        let out_dir = std::env::var("OUT_DIR").unwrap();
+
+        let ranges = std::env::var(RANGES_VAR).unwrap_or_else(|_| DEFAULT_RANGES.to_owned());
+        let ranges = parse_ranges(&ranges);
+        for (feature, path) in TEXT_FONTS {
+            if feature_enabled(feature) {
+                subset_font(path, &out_dir, |c| {
+                    ranges.iter().any(|range| range.contains(&(c as u32)))
+                });
+            }
+        }
+
+        let known_icons = parse_icons_module(&std::fs::read_to_string("src/icons.rs").unwrap());
+        let icons = match std::env::var(ICONS_VAR) {
+            Ok(icons) => parse_icons(&icons, &known_icons),
+            Err(_) => known_icons.values().copied().collect(),
+        };
+        for (feature, path) in ICON_FONTS {
+            if feature_enabled(feature) {
+                subset_font(path, &out_dir, |c| icons.contains(&c));
+            }
+        }
+    }
+
+    fn feature_enabled(feature: &str) -> bool {
+        std::env::var_os(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_some()
+    }
+
+    /// Subset the font at `src/{path}` to the characters accepted by `keep`, and write it to
+    /// `{out_dir}/{path}`.
+    fn subset_font(path: &str, out_dir: &str, keep: impl Fn(char) -> bool) {
+        let src = Path::new("src").join(path);
+        println!("cargo:rerun-if-changed={}", src.display());
+
+        let data = std::fs::read(&src).unwrap();
+        let face = ttf_parser::Face::parse(&data, 0)
+            .unwrap_or_else(|err| panic!("Failed to parse {}: {err}", src.display()));
+
+        // Glyph 0 is the .notdef glyph and must always be kept.
+        let mut glyphs = vec![0];
+        if let Some(cmap) = face.tables().cmap {
+            for subtable in cmap.subtables.into_iter().filter(|s| s.is_unicode()) {
+                subtable.codepoints(|codepoint| {
+                    if char::from_u32(codepoint).is_some_and(&keep) {
+                        if let Some(glyph) = subtable.glyph_index(codepoint) {
+                            glyphs.push(glyph.0);
+                        }
+                    }
+                });
+            }
+        }
+        glyphs.sort_unstable();
+        glyphs.dedup();
+
+        let subset = subsetter::subset(&data, 0, subsetter::Profile::pdf(&glyphs))
+            .unwrap_or_else(|err| panic!("Failed to subset {}: {err}", src.display()));
+
+        let dst = Path::new(out_dir).join(path);
+        std::fs::create_dir_all(dst.parent().unwrap()).unwrap();
+        std::fs::write(&dst, subset).unwrap();
+    }
+
+    /// Parse a list of unicode ranges like `0020-007E,20AC`.
+    fn parse_ranges(ranges: &str) -> Vec<RangeInclusive<u32>> {
+        ranges
+            .split(',')
+            .map(str::trim)
+            .filter(|range| !range.is_empty())
+            .map(|range| {
+                let (start, end) = range.split_once('-').unwrap_or((range, range));
+                parse_codepoint(start)..=parse_codepoint(end)
+            })
+            .collect()
+    }
+
+    fn parse_codepoint(codepoint: &str) -> u32 {
+        let hex = codepoint.trim();
+        let hex = hex
+            .strip_prefix("U+")
+            .or_else(|| hex.strip_prefix("u+"))
+            .unwrap_or(hex);
+        u32::from_str_radix(hex, 16)
+            .unwrap_or_else(|_| panic!("Invalid unicode codepoint: {codepoint}"))
+    }
+
+    /// Parse a list of icons, given by name or by codepoint.
+    fn parse_icons(icons: &str, known_icons: &BTreeMap<String, char>) -> Vec<char> {
+        icons
+            .split(',')
+            .map(str::trim)
+            .filter(|icon| !icon.is_empty())
+            .map(|icon| {
+                if icon.starts_with("U+") || icon.starts_with("u+") {
+                    char::from_u32(parse_codepoint(icon))
+                        .unwrap_or_else(|| panic!("Invalid unicode codepoint: {icon}"))
+                } else {
+                    *known_icons
+                        .get(&icon.to_uppercase().replace('-', "_"))
+                        .unwrap_or_else(|| panic!("Unknown icon: {icon}"))
+                }
+            })
+            .collect()
+    }
+
+    /// Find the `pub const NAME: Icon = Icon::new('\u{XXXX}');` declarations of the icons module.
+    fn parse_icons_module(source: &str) -> BTreeMap<String, char> {
+        source
+            .lines()
+            .filter_map(|line| {
+                let (name, value) = line.strip_prefix("pub const ")?.split_once(": Icon = ")?;
+                let codepoint = value.split_once("'\\u{")?.1.split_once('}')?.0;
+                Some((name.to_owned(), char::from_u32(parse_codepoint(codepoint))?))
+            })
+            .collect()
+    }
+}
@@ -2,6 +2,10 @@ use eframe::{App, Frame, NativeOptions};
 use egui::{CentralPanel, ComboBox, Context, FontFamily, FontId, Slider, TextFormat};
 use egui::text::LayoutJob;
 
+use egui_ui_refresh::fonts::{
+    font_family_bold, font_family_bold_italic, font_family_italic, font_family_medium,
+    font_family_semibold,
+};
 use egui_ui_refresh::RefreshedTheme;
 
 fn main() {
@@ -11,13 +15,12 @@ fn main() {
             ..Default::default()
         },
         Box::new(|cc| {
-            cc.egui_ctx.set_fonts(egui_ui_refresh::fonts());
-            RefreshedTheme::init_default().apply(&cc.egui_ctx);
+            RefreshedTheme::init_default()
+                .apply_with_fonts(&cc.egui_ctx, egui_ui_refresh::fonts::fonts());
 
             Ok(Box::new(ExampleApp {
                 font_family: FontFamily::Proportional,
                 font_size: 30.0,
-                extra_spacing: false,
             }))
         }),
     )
@@ -27,7 +30,6 @@ fn main() {
 struct ExampleApp {
     font_family: FontFamily,
     font_size: f32,
-    extra_spacing: bool,
 }
 
 impl App for ExampleApp {
@@ -43,15 +45,14 @@ impl App for ExampleApp {
                         "Proportional",
                     );
                     ui.selectable_value(&mut self.font_family, FontFamily::Monospace, "Monospace");
+                    ui.selectable_value(&mut self.font_family, font_family_italic(), "Italic");
+                    ui.selectable_value(&mut self.font_family, font_family_medium(), "Medium");
+                    ui.selectable_value(&mut self.font_family, font_family_semibold(), "SemiBold");
+                    ui.selectable_value(&mut self.font_family, font_family_bold(), "Bold");
                     ui.selectable_value(
                         &mut self.font_family,
-                        FontFamily::Name(egui_ui_refresh::ITALIC.into()),
-                        egui_ui_refresh::ITALIC,
-                    );
-                    ui.selectable_value(
-                        &mut self.font_family,
-                        FontFamily::Name(egui_ui_refresh::MEDIUM.into()),
-                        egui_ui_refresh::MEDIUM,
+                        font_family_bold_italic(),
+                        "Bold italic",
                     );
                 });
             ui.add_space(4.0);
@@ -72,7 +73,7 @@ impl App for ExampleApp {
                 },
             );
             ui.label(job).on_hover_ui(|ui| {
-                ui.link("Hey !");
+                let _ = ui.link("Hey !");
             });
         });
     }
@@ -26,8 +26,8 @@ fn main() {
             ..Default::default()
         },
         Box::new(|cc| {
-            cc.egui_ctx.set_fonts(egui_ui_refresh::fonts::fonts());
-            RefreshedTheme::init_default().apply(&cc.egui_ctx);
+            RefreshedTheme::init_default()
+                .apply_with_fonts(&cc.egui_ctx, egui_ui_refresh::fonts::fonts());
 
             Ok(Box::new(ExampleApp {
                 toasts: Toasts::new(),
@@ -59,7 +59,7 @@ impl App for ExampleApp {
             if ui.button("Modal").clicked() {
                 self.modal_handler.open();
             }
-            self.modal_handler.ui(ctx, || { Modal::new("Modal") }, |ui, close| {
+            self.modal_handler.ui(ctx, || { Modal::new("Modal") }, |ui, _close| {
                 ui.label("modal content")
             });
         });
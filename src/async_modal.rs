@@ -0,0 +1,262 @@
+//! Modals requested by non-UI code, which awaits their result.
+//!
+//! An [`AsyncModalHandler`] lives in the UI, and shows the modals requested through its
+//! [`ModalRequester`]s one at a time. Each request returns a [`ModalFuture`], resolving to the
+//! result the modal completed with, or to `None` if it was dismissed. The future doesn't depend
+//! on an async runtime, and can also be polled from synchronous code with
+//! [`ModalFuture::try_take`].
+//!
+//! ```
+//! # use egui_ui_refresh::async_modal::{AsyncModalHandler, ModalRequester};
+//! # use egui_ui_refresh::modal::Modal;
+//! async fn delete_file(confirm: ModalRequester<String, bool>, path: String) {
+//!     if confirm.request(format!("Delete {path}?")).await == Some(true) {
+//!         // delete the file
+//!     }
+//! }
+//!
+//! let mut confirm_modal = AsyncModalHandler::<String, bool>::default();
+//! // Typically spawned on the runtime of the app
+//! let task = delete_file(confirm_modal.requester(), "report.pdf".to_owned());
+//!
+//! # egui::__run_test_ui(|ui| {
+//! confirm_modal.show(ui.ctx(), |_| Modal::new("Confirm"), |ui, question| {
+//!     ui.label(question.as_str());
+//!     ui.horizontal(|ui| {
+//!         if ui.button("Yes").clicked() {
+//!             return Some(true);
+//!         }
+//!         ui.button("No").clicked().then_some(false)
+//!     })
+//!     .inner
+//! });
+//! # });
+//! # drop(task);
+//! ```
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use crate::modal::{Modal, ModalHandler};
+
+/// Shows the modals requested through its [`ModalRequester`]s, one at a time, on top of a
+/// [`ModalHandler`].
+pub struct AsyncModalHandler<T, R> {
+    handler: ModalHandler<T, R>,
+    requests: Arc<Mutex<Requests<T, R>>>,
+
+    /// Resolves the future of the modal being shown.
+    current: Option<Completer<R>>,
+}
+
+struct Requests<T, R> {
+    queue: VecDeque<(T, Completer<R>)>,
+
+    /// Repainted when a request comes in, once the handler was shown.
+    ctx: Option<egui::Context>,
+
+    /// Whether the handler was dropped, new requests resolving to `None` right away.
+    closed: bool,
+}
+
+impl<T, R> Default for AsyncModalHandler<T, R> {
+    fn default() -> Self {
+        Self {
+            handler: ModalHandler::default(),
+            requests: Arc::new(Mutex::new(Requests {
+                queue: VecDeque::new(),
+                ctx: None,
+                closed: false,
+            })),
+            current: None,
+        }
+    }
+}
+
+impl<T, R> AsyncModalHandler<T, R> {
+    /// A handle to request modals from anywhere, e.g. another thread or an async task.
+    pub fn requester(&self) -> ModalRequester<T, R> {
+        ModalRequester {
+            requests: self.requests.clone(),
+        }
+    }
+
+    /// Whether a modal is open, or will open next time [`AsyncModalHandler::show`] is called.
+    pub fn is_open(&self) -> bool {
+        self.handler.is_open() || !lock(&self.requests).queue.is_empty()
+    }
+
+    /// Show the modal of the oldest request, like [`ModalHandler::show`] with the payload of the
+    /// request, and resolve its future when it closes.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        make_modal: impl FnOnce(&T) -> Modal,
+        content_ui: impl FnOnce(&mut egui::Ui, &mut T) -> Option<R>,
+    ) {
+        {
+            let mut requests = lock(&self.requests);
+            requests.ctx = Some(ctx.clone());
+            while self.current.is_none() {
+                let Some((payload, completer)) = requests.queue.pop_front() else {
+                    break;
+                };
+                // Nobody awaits the result anymore
+                if completer.is_cancelled() {
+                    continue;
+                }
+                self.handler.open_with(payload);
+                self.current = Some(completer);
+            }
+        }
+
+        self.handler.show(ctx, make_modal, content_ui);
+
+        if !self.handler.is_open() {
+            if let Some(completer) = self.current.take() {
+                completer.resolve(self.handler.take_result());
+                // Show the next request without waiting for an input
+                ctx.request_repaint();
+            }
+        }
+    }
+}
+
+impl<T, R> Drop for AsyncModalHandler<T, R> {
+    fn drop(&mut self) {
+        let queue = {
+            let mut requests = lock(&self.requests);
+            requests.closed = true;
+            requests.ctx = None;
+            std::mem::take(&mut requests.queue)
+        };
+        // Resolve the pending futures to `None` outside of the lock, in case their waker requests
+        // another modal right away
+        drop(queue);
+        self.current = None;
+    }
+}
+
+/// Requests modals from an [`AsyncModalHandler`].
+pub struct ModalRequester<T, R> {
+    requests: Arc<Mutex<Requests<T, R>>>,
+}
+
+impl<T, R> Clone for ModalRequester<T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            requests: self.requests.clone(),
+        }
+    }
+}
+
+impl<T, R> ModalRequester<T, R> {
+    /// Open a modal with `payload` once the modals requested before are closed.
+    ///
+    /// Dropping the future before the modal opens cancels the request. Once the
+    /// [`AsyncModalHandler`] is dropped, the future resolves to `None` right away.
+    pub fn request(&self, payload: T) -> ModalFuture<R> {
+        let state = Arc::new(Mutex::new(FutureState {
+            result: None,
+            waker: None,
+        }));
+
+        let mut requests = lock(&self.requests);
+        if requests.closed {
+            lock(&state).result = Some(None);
+            return ModalFuture { state };
+        }
+        requests.queue.push_back((
+            payload,
+            Completer {
+                state: Some(state.clone()),
+            },
+        ));
+        if let Some(ctx) = &requests.ctx {
+            ctx.request_repaint();
+        }
+
+        ModalFuture { state }
+    }
+}
+
+/// The result of a modal requested with [`ModalRequester::request`]: the result it completed
+/// with, or `None` if it was dismissed or its [`AsyncModalHandler`] dropped.
+#[must_use = "The modal is not requested anymore once its future is dropped"]
+pub struct ModalFuture<R> {
+    state: Arc<Mutex<FutureState<R>>>,
+}
+
+struct FutureState<R> {
+    result: Option<Option<R>>,
+    waker: Option<Waker>,
+}
+
+impl<R> ModalFuture<R> {
+    /// The result of the modal if it is closed, for code that can't await it.
+    ///
+    /// Returns `None` while the modal is open, and then `Some` once.
+    pub fn try_take(&mut self) -> Option<Option<R>> {
+        lock(&self.state).result.take()
+    }
+}
+
+impl<R> Future for ModalFuture<R> {
+    type Output = Option<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.state);
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Resolves a [`ModalFuture`], to `None` if dropped before.
+struct Completer<R> {
+    state: Option<Arc<Mutex<FutureState<R>>>>,
+}
+
+impl<R> Completer<R> {
+    fn is_cancelled(&self) -> bool {
+        self.state
+            .as_ref()
+            .map_or(true, |state| Arc::strong_count(state) == 1)
+    }
+
+    fn resolve(mut self, result: Option<R>) {
+        self.resolve_with(result);
+    }
+
+    fn resolve_with(&mut self, result: Option<R>) {
+        let Some(state) = self.state.take() else {
+            return;
+        };
+        let waker = {
+            let mut state = lock(&state);
+            state.result = Some(result);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<R> Drop for Completer<R> {
+    fn drop(&mut self) {
+        self.resolve_with(None);
+    }
+}
+
+/// Lock `mutex`, even if a thread panicked while holding it since its data stays consistent.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
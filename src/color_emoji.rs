@@ -0,0 +1,234 @@
+//! Color emoji rendered from a sprite atlas, as inline images within text.
+//!
+//! The bundled Noto Emoji font is monochrome. For chat-like or notification UIs, an application
+//! can load a color emoji sprite sheet (e.g. Twemoji or Noto Color Emoji exported as a grid) in
+//! an [`EmojiAtlas`], and display text with [`EmojiLabel`]: every emoji found in the atlas is
+//! drawn as an image sized like the text, the rest of the text is a regular label.
+
+use std::collections::HashMap;
+
+use egui::{
+    Color32, ColorImage, Context, Image, Pos2, Rect, Response, RichText, TextStyle, TextureHandle,
+    TextureOptions, Ui, Vec2, Widget,
+};
+
+/// Variation selector forcing the emoji presentation of a character, ignored for lookups.
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+/// A grid of emoji sprites in a single texture.
+///
+/// ```
+/// # use egui_ui_refresh::color_emoji::{EmojiAtlas, EmojiLabel};
+/// # egui::__run_test_ui(|ui| {
+/// # let sprite_sheet = egui::ColorImage::new([64, 32], egui::Color32::TRANSPARENT);
+/// // A 2x1 grid of 32px sprites
+/// let atlas = EmojiAtlas::from_grid(ui.ctx(), "emoji", sprite_sheet, 32, ["😀", "👍"]);
+/// ui.add(EmojiLabel::new("Nice 👍", &atlas));
+/// # });
+/// ```
+///
+/// Text is split between emojis of the atlas and regular text runs:
+///
+/// ```
+/// # use egui_ui_refresh::color_emoji::{EmojiAtlas, Segment};
+/// # egui::__run_test_ui(|ui| {
+/// # let sprite_sheet = egui::ColorImage::new([64, 32], egui::Color32::TRANSPARENT);
+/// let atlas = EmojiAtlas::from_grid(ui.ctx(), "emoji", sprite_sheet, 32, ["❤", "👍🏽"]);
+/// let segments = atlas.segments("I ❤️ it 👍🏽!");
+/// assert_eq!(segments.len(), 5);
+/// assert_eq!(segments[1], Segment::Emoji { emoji: "❤️", uv: atlas.uv("❤").unwrap() });
+/// assert_eq!(segments[3], Segment::Emoji { emoji: "👍🏽", uv: atlas.uv("👍🏽").unwrap() });
+/// assert_eq!(segments[4], Segment::Text("!"));
+/// # });
+/// ```
+pub struct EmojiAtlas {
+    texture: TextureHandle,
+    sprites: HashMap<String, Rect>,
+    max_sequence_len: usize,
+}
+
+impl EmojiAtlas {
+    /// Load a sprite sheet of square `cell_size` sprites, `emojis` giving the emoji of each cell
+    /// in row-major order.
+    pub fn from_grid<'a>(
+        ctx: &Context,
+        name: impl Into<String>,
+        image: ColorImage,
+        cell_size: usize,
+        emojis: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let [width, height] = image.size;
+        let columns = (width / cell_size).max(1);
+        let cell_uv = Vec2::new(
+            cell_size as f32 / width as f32,
+            cell_size as f32 / height as f32,
+        );
+
+        let sprites = emojis.into_iter().enumerate().map(|(i, emoji)| {
+            let min = Pos2::new(
+                (i % columns) as f32 * cell_uv.x,
+                (i / columns) as f32 * cell_uv.y,
+            );
+            (emoji, Rect::from_min_size(min, cell_uv))
+        });
+        Self::new(ctx, name, image, sprites)
+    }
+
+    /// Load a sprite sheet with the uv rect (in `0..=1` texture coordinates) of each emoji.
+    pub fn new<'a>(
+        ctx: &Context,
+        name: impl Into<String>,
+        image: ColorImage,
+        sprites: impl IntoIterator<Item = (&'a str, Rect)>,
+    ) -> Self {
+        let texture = ctx.load_texture(name, image, TextureOptions::LINEAR);
+        let sprites: HashMap<String, Rect> = sprites
+            .into_iter()
+            .map(|(emoji, uv)| (normalize(emoji.chars()), uv))
+            .collect();
+        let max_sequence_len = sprites
+            .keys()
+            .map(|emoji| emoji.chars().count())
+            .max()
+            .unwrap_or(0);
+        Self {
+            texture,
+            sprites,
+            max_sequence_len,
+        }
+    }
+
+    /// Texture coordinates of `emoji`, if it's part of the atlas.
+    pub fn uv(&self, emoji: &str) -> Option<Rect> {
+        self.sprites.get(&normalize(emoji.chars())).copied()
+    }
+
+    /// Split `text` into text runs and emojis of the atlas.
+    ///
+    /// Emoji sequences (ZWJ sequences, flags, skin tones) are matched as a whole when the atlas
+    /// contains them, the emoji presentation selector `U+FE0F` is ignored.
+    pub fn segments<'t>(&self, text: &'t str) -> Vec<Segment<'t>> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut segments = Vec::new();
+        let mut text_start = 0;
+        let mut i = 0;
+        while i < chars.len() {
+            let longest = (1..=self.max_sequence_len.min(chars.len() - i))
+                .rev()
+                .find_map(|len| {
+                    let key = normalize(chars[i..i + len].iter().map(|(_, c)| *c));
+                    self.sprites.get(&key).map(|uv| (len, *uv))
+                });
+
+            let Some((mut len, uv)) = longest else {
+                i += 1;
+                continue;
+            };
+            if chars.get(i + len).is_some_and(|(_, c)| *c == EMOJI_PRESENTATION) {
+                len += 1;
+            }
+
+            let start = chars[i].0;
+            let end = chars.get(i + len).map_or(text.len(), |(index, _)| *index);
+            if text_start < start {
+                segments.push(Segment::Text(&text[text_start..start]));
+            }
+            segments.push(Segment::Emoji {
+                emoji: &text[start..end],
+                uv,
+            });
+            text_start = end;
+            i += len;
+        }
+        if text_start < text.len() {
+            segments.push(Segment::Text(&text[text_start..]));
+        }
+        segments
+    }
+
+    /// The emoji as an image of the given size.
+    pub fn image(&self, uv: Rect, size: f32) -> Image<'static> {
+        Image::new((self.texture.id(), Vec2::splat(size))).uv(uv)
+    }
+}
+
+/// A part of a text split by [`EmojiAtlas::segments`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment<'t> {
+    Text(&'t str),
+    Emoji { emoji: &'t str, uv: Rect },
+}
+
+fn normalize(chars: impl Iterator<Item = char>) -> String {
+    chars.filter(|c| *c != EMOJI_PRESENTATION).collect()
+}
+
+/// A label showing the emojis of an [`EmojiAtlas`] in color.
+///
+/// Emojis are sized like the text, according to its [`TextStyle`].
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub struct EmojiLabel<'a> {
+    text: String,
+    atlas: &'a EmojiAtlas,
+    text_style: Option<TextStyle>,
+    color: Option<Color32>,
+}
+
+impl<'a> EmojiLabel<'a> {
+    pub fn new(text: impl Into<String>, atlas: &'a EmojiAtlas) -> Self {
+        Self {
+            text: text.into(),
+            atlas,
+            text_style: None,
+            color: None,
+        }
+    }
+
+    /// Text style of the label, defaults to the ui text style.
+    #[inline]
+    pub fn text_style(mut self, text_style: TextStyle) -> Self {
+        self.text_style = Some(text_style);
+        self
+    }
+
+    /// Color of the text, the emojis keep their colors.
+    #[inline]
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+}
+
+impl Widget for EmojiLabel<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            text,
+            atlas,
+            text_style,
+            color,
+        } = self;
+        let text_style = text_style
+            .or_else(|| ui.style().override_text_style.clone())
+            .unwrap_or(TextStyle::Body);
+        let emoji_size = ui.text_style_height(&text_style);
+
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            for segment in atlas.segments(&text) {
+                match segment {
+                    Segment::Text(run) => {
+                        let mut run = RichText::new(run).text_style(text_style.clone());
+                        if let Some(color) = color {
+                            run = run.color(color);
+                        }
+                        ui.label(run);
+                    }
+                    Segment::Emoji { emoji, uv } => {
+                        ui.add(atlas.image(uv, emoji_size)).on_hover_text(emoji);
+                    }
+                }
+            }
+        })
+        .response
+    }
+}
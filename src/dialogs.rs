@@ -0,0 +1,436 @@
+//! Ready-made dialogs on top of [`Modal`]: confirmation, alert and text prompt.
+//!
+//! A dialog is shown every frame through a [`ModalHandler`], like any modal, which it completes
+//! with a [`DialogResult`] taken with [`ModalHandler::take_result`].
+
+use egui::{Color32, Context, RichText, Ui};
+
+use crate::icons::{self, Icon};
+use crate::modal::{FooterAction, Modal, ModalFooter, ModalHandler};
+use crate::{ERROR_COLOR, INFO_COLOR, WARNING_COLOR};
+
+const DIALOG_MIN_WIDTH: f32 = 320.0;
+
+const ICON_SIZE: f32 = 24.0;
+
+/// How a dialog was closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogResult<T> {
+    /// The primary button was clicked, with the value of the dialog.
+    Primary(T),
+
+    /// The secondary button (e.g. "Cancel") was clicked.
+    Secondary,
+
+    /// The dialog was dismissed with Escape, a click outside or the close button.
+    Dismissed,
+}
+
+impl<T> DialogResult<T> {
+    /// The value of the dialog if its primary button was clicked.
+    pub fn primary(self) -> Option<T> {
+        match self {
+            Self::Primary(value) => Some(value),
+            Self::Secondary | Self::Dismissed => None,
+        }
+    }
+}
+
+/// A question with a primary and a secondary button.
+///
+/// ```
+/// # use egui_ui_refresh::dialogs::{ConfirmDialog, DialogResult};
+/// # use egui_ui_refresh::modal::ModalHandler;
+/// let mut delete_dialog = ModalHandler::default();
+///
+/// # egui::__run_test_ui(|ui| {
+/// if ui.button("Delete").clicked() {
+///     delete_dialog.open();
+/// }
+///
+/// ConfirmDialog::new("Delete file?", "This can't be undone.")
+///     .primary("Delete")
+///     .destructive(true)
+///     .show(ui.ctx(), &mut delete_dialog);
+/// if let Some(DialogResult::Primary(())) = delete_dialog.take_result() {
+///     // delete the file
+/// }
+/// # });
+/// ```
+pub struct ConfirmDialog {
+    id: egui::Id,
+    title: String,
+    message: String,
+    primary: String,
+    secondary: String,
+    destructive: bool,
+}
+
+impl ConfirmDialog {
+    /// A dialog with "OK" and "Cancel" buttons.
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        let title = title.into();
+        Self {
+            id: egui::Id::new(&title),
+            title,
+            message: message.into(),
+            primary: "OK".to_owned(),
+            secondary: "Cancel".to_owned(),
+            destructive: false,
+        }
+    }
+
+    /// Set the id of the dialog, the title by default, see [`Modal::id`].
+    #[inline]
+    pub fn id(mut self, id: egui::Id) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Label of the primary button.
+    #[inline]
+    pub fn primary(mut self, primary: impl Into<String>) -> Self {
+        self.primary = primary.into();
+        self
+    }
+
+    /// Label of the secondary button.
+    #[inline]
+    pub fn secondary(mut self, secondary: impl Into<String>) -> Self {
+        self.secondary = secondary.into();
+        self
+    }
+
+    /// Show the primary button in red, for actions that destroy data.
+    #[inline]
+    pub fn destructive(mut self, destructive: bool) -> Self {
+        self.destructive = destructive;
+        self
+    }
+
+    /// Show the dialog while `handler` is open, completing it with how it was closed.
+    pub fn show(self, ctx: &Context, handler: &mut ModalHandler<(), DialogResult<()>>) {
+        let Self {
+            id,
+            title,
+            message,
+            primary,
+            secondary,
+            destructive,
+        } = self;
+        show_dialog(ctx, handler, id, &title, None, |ui| {
+            ui.label(message);
+            let mut footer = ModalFooter::new(primary).secondary(secondary);
+            if destructive {
+                footer = footer.primary_color(ERROR_COLOR);
+            }
+            footer_result(footer.show(ui))
+        })
+    }
+}
+
+/// The severity of an [`AlertDialog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertKind {
+    Info,
+    Warning,
+    Error,
+}
+
+impl AlertKind {
+    pub fn icon(self) -> Icon {
+        match self {
+            Self::Info => icons::INFO,
+            Self::Warning => icons::WARNING,
+            Self::Error => icons::WARNING_CIRCLE,
+        }
+    }
+
+    pub fn color(self) -> Color32 {
+        match self {
+            Self::Info => INFO_COLOR,
+            Self::Warning => WARNING_COLOR,
+            Self::Error => ERROR_COLOR,
+        }
+    }
+}
+
+/// A message with an icon and a single button.
+///
+/// ```
+/// # use egui_ui_refresh::dialogs::{AlertDialog, AlertKind};
+/// # use egui_ui_refresh::modal::ModalHandler;
+/// let mut error_dialog = ModalHandler::default();
+///
+/// # egui::__run_test_ui(|ui| {
+/// AlertDialog::new(AlertKind::Error, "Export failed", "The disk is full.")
+///     .show(ui.ctx(), &mut error_dialog);
+/// # });
+/// ```
+pub struct AlertDialog {
+    id: egui::Id,
+    kind: AlertKind,
+    title: String,
+    message: String,
+    button: String,
+}
+
+impl AlertDialog {
+    /// An alert with an "OK" button.
+    pub fn new(kind: AlertKind, title: impl Into<String>, message: impl Into<String>) -> Self {
+        let title = title.into();
+        Self {
+            id: egui::Id::new(&title),
+            kind,
+            title,
+            message: message.into(),
+            button: "OK".to_owned(),
+        }
+    }
+
+    /// Set the id of the dialog, the title by default, see [`Modal::id`].
+    #[inline]
+    pub fn id(mut self, id: egui::Id) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Label of the button.
+    #[inline]
+    pub fn button(mut self, button: impl Into<String>) -> Self {
+        self.button = button.into();
+        self
+    }
+
+    /// Show the alert while `handler` is open, completing it with [`DialogResult::Primary`] when
+    /// its button is clicked, or [`DialogResult::Dismissed`].
+    pub fn show(self, ctx: &Context, handler: &mut ModalHandler<(), DialogResult<()>>) {
+        let Self {
+            id,
+            kind,
+            title,
+            message,
+            button,
+        } = self;
+        show_dialog(ctx, handler, id, &title, None, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(kind.icon().with_size(ICON_SIZE).image().tint(kind.color()));
+                ui.label(message);
+            });
+            footer_result(ModalFooter::new(button).show(ui))
+        })
+    }
+}
+
+/// A single-line text input with a primary and a secondary button.
+///
+/// The input has the focus when the dialog opens. Its text is kept while the dialog is open, and
+/// returned when the primary button is clicked.
+/// The primary button is disabled while the validation fails.
+///
+/// ```
+/// # use egui_ui_refresh::dialogs::PromptDialog;
+/// # use egui_ui_refresh::modal::ModalHandler;
+/// let mut rename_dialog = ModalHandler::default();
+/// let existing = vec!["main".to_owned()];
+///
+/// # egui::__run_test_ui(|ui| {
+/// PromptDialog::new("Rename branch", "New name:")
+///     .initial_text("main")
+///     .validate(|name| {
+///         if name.is_empty() {
+///             Err("The name can't be empty".to_owned())
+///         } else if existing.iter().any(|branch| branch == name) {
+///             Err(format!("{name} already exists"))
+///         } else {
+///             Ok(())
+///         }
+///     })
+///     .show(ui.ctx(), &mut rename_dialog);
+/// if let Some(new_name) = rename_dialog.take_result().and_then(|result| result.primary()) {
+///     // rename the branch
+/// }
+/// # });
+/// ```
+pub struct PromptDialog<'a> {
+    id: egui::Id,
+    title: String,
+    message: String,
+    initial_text: String,
+    hint_text: String,
+    primary: String,
+    secondary: String,
+    validate: Option<Box<Validate<'a>>>,
+}
+
+/// Checks the text of a [`PromptDialog`], returning the error to show.
+type Validate<'a> = dyn Fn(&str) -> Result<(), String> + 'a;
+
+impl<'a> PromptDialog<'a> {
+    /// A prompt with "OK" and "Cancel" buttons.
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        let title = title.into();
+        Self {
+            id: egui::Id::new(&title),
+            title,
+            message: message.into(),
+            initial_text: String::new(),
+            hint_text: String::new(),
+            primary: "OK".to_owned(),
+            secondary: "Cancel".to_owned(),
+            validate: None,
+        }
+    }
+
+    /// Set the id of the dialog, the title by default, see [`Modal::id`].
+    #[inline]
+    pub fn id(mut self, id: egui::Id) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Text when the dialog opens.
+    #[inline]
+    pub fn initial_text(mut self, initial_text: impl Into<String>) -> Self {
+        self.initial_text = initial_text.into();
+        self
+    }
+
+    /// Text shown while the input is empty.
+    #[inline]
+    pub fn hint_text(mut self, hint_text: impl Into<String>) -> Self {
+        self.hint_text = hint_text.into();
+        self
+    }
+
+    /// Label of the primary button.
+    #[inline]
+    pub fn primary(mut self, primary: impl Into<String>) -> Self {
+        self.primary = primary.into();
+        self
+    }
+
+    /// Label of the secondary button.
+    #[inline]
+    pub fn secondary(mut self, secondary: impl Into<String>) -> Self {
+        self.secondary = secondary.into();
+        self
+    }
+
+    /// Check the text, the error being shown under the input.
+    #[inline]
+    pub fn validate(mut self, validate: impl Fn(&str) -> Result<(), String> + 'a) -> Self {
+        self.validate = Some(Box::new(validate));
+        self
+    }
+
+    /// Show the prompt while `handler` is open, completing it with the text when the primary
+    /// button is clicked (or Enter pressed).
+    pub fn show(self, ctx: &Context, handler: &mut ModalHandler<(), DialogResult<String>>) {
+        let Self {
+            id,
+            title,
+            message,
+            initial_text,
+            hint_text,
+            primary,
+            secondary,
+            validate,
+        } = self;
+        let text_id = id.with("prompt_text");
+        if !handler.is_visible() {
+            return;
+        }
+        let is_new = ctx.data(|data| {
+            data.get_temp::<PromptText>(text_id)
+                .map_or(true, |prompt| prompt.closed)
+        });
+        if handler.is_open() && is_new {
+            let prompt = PromptText {
+                text: initial_text,
+                closed: false,
+            };
+            ctx.data_mut(|data| data.insert_temp(text_id, prompt));
+        }
+
+        let edit_id = text_id.with("edit");
+        show_dialog(ctx, handler, id, &title, Some(edit_id), |ui| {
+            let PromptText { mut text, closed } = ui
+                .data(|data| data.get_temp::<PromptText>(text_id))
+                .unwrap_or_default();
+
+            ui.label(message);
+            ui.add(
+                egui::TextEdit::singleline(&mut text)
+                    .id(edit_id)
+                    .hint_text(hint_text)
+                    .desired_width(f32::INFINITY),
+            );
+            let validation = validate.as_ref().map_or(Ok(()), |validate| validate(&text));
+            if let Err(error) = &validation {
+                ui.label(RichText::new(error).color(ERROR_COLOR));
+            }
+
+            let action = ModalFooter::new(primary)
+                .secondary(secondary)
+                .primary_enabled(validation.is_ok())
+                .show(ui);
+            let result = footer_result(action).map(|result| match result {
+                DialogResult::Primary(()) => DialogResult::Primary(text.clone()),
+                DialogResult::Secondary => DialogResult::Secondary,
+                DialogResult::Dismissed => DialogResult::Dismissed,
+            });
+            ui.data_mut(|data| data.insert_temp(text_id, PromptText { text, closed }));
+            result
+        });
+
+        // The text is kept during the close animation
+        if !handler.is_visible() {
+            ctx.data_mut(|data| data.remove::<PromptText>(text_id));
+        } else if !handler.is_open() {
+            ctx.data_mut(|data| data.get_temp_mut_or_default::<PromptText>(text_id).closed = true);
+        }
+    }
+}
+
+/// The text of an open [`PromptDialog`].
+#[derive(Clone, Default)]
+struct PromptText {
+    text: String,
+
+    /// Whether the dialog was closed and plays its close animation, the text being reset if it
+    /// opens again.
+    closed: bool,
+}
+
+/// Show the dialog `content` in the modal of `handler`, completing it with the result the
+/// content returns, or with [`DialogResult::Dismissed`].
+fn show_dialog<T>(
+    ctx: &Context,
+    handler: &mut ModalHandler<(), DialogResult<T>>,
+    id: egui::Id,
+    title: &str,
+    initial_focus: Option<egui::Id>,
+    content: impl FnOnce(&mut Ui) -> Option<DialogResult<T>>,
+) {
+    let was_open = handler.is_open();
+    handler.show(
+        ctx,
+        |()| {
+            let modal = Modal::new(title).id(id).min_width(DIALOG_MIN_WIDTH);
+            match initial_focus {
+                Some(id) => modal.initial_focus(id),
+                None => modal,
+            }
+        },
+        |ui, ()| content(ui),
+    );
+    handler.complete_if_closed(was_open, || DialogResult::Dismissed);
+}
+
+fn footer_result(action: Option<FooterAction>) -> Option<DialogResult<()>> {
+    action.map(|action| match action {
+        FooterAction::Primary => DialogResult::Primary(()),
+        FooterAction::Secondary => DialogResult::Secondary,
+    })
+}
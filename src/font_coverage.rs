@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use ab_glyph::{Font, FontRef};
+use egui::{FontDefinitions, FontFamily};
+
+/// Finds which font of a [`FontDefinitions`] renders each character, to catch missing glyphs
+/// (rendered as tofu) before they reach users.
+///
+/// ```
+/// # use egui::FontFamily;
+/// # use egui_ui_refresh::font_coverage::FontCoverage;
+/// # #[cfg(feature = "fonts")] {
+/// let fonts = egui_ui_refresh::fonts::fonts();
+/// let coverage = FontCoverage::new(&fonts);
+///
+/// let report = coverage.check("Hé 🙂 中");
+/// assert_eq!(report.font_for(&FontFamily::Proportional, 'H'), Some("Inter-Regular"));
+/// assert_eq!(report.font_for(&FontFamily::Proportional, '🙂'), Some("NotoEmoji"));
+/// assert_eq!(report.missing(), vec!['中']);
+/// # }
+/// ```
+pub struct FontCoverage<'a> {
+    families: &'a BTreeMap<FontFamily, Vec<String>>,
+    fonts: BTreeMap<&'a str, FontRef<'a>>,
+}
+
+impl<'a> FontCoverage<'a> {
+    /// Fonts that fail to parse are treated as having no glyph at all.
+    pub fn new(definitions: &'a FontDefinitions) -> Self {
+        let fonts = definitions
+            .font_data
+            .iter()
+            .filter_map(|(name, data)| {
+                let font = FontRef::try_from_slice_and_index(&data.font, data.index).ok()?;
+                Some((name.as_str(), font))
+            })
+            .collect();
+        Self {
+            families: &definitions.families,
+            fonts,
+        }
+    }
+
+    /// The font of the fallback chain of `family` serving `c`, if any.
+    pub fn font_for(&self, family: &FontFamily, c: char) -> Option<&'a str> {
+        self.families.get(family)?.iter().find_map(|name| {
+            let (name, font) = self.fonts.get_key_value(name.as_str())?;
+            (font.glyph_id(c).0 != 0).then_some(*name)
+        })
+    }
+
+    /// Check every character of `text`, in all the families.
+    pub fn check(&self, text: &str) -> CoverageReport {
+        self.check_corpus([text])
+    }
+
+    /// Check every character of a text corpus (e.g. all the translations of an application), in
+    /// all the families.
+    pub fn check_corpus<'t>(&self, corpus: impl IntoIterator<Item = &'t str>) -> CoverageReport {
+        let mut glyphs = BTreeMap::new();
+        for c in corpus.into_iter().flat_map(str::chars) {
+            // Control characters (newlines, tabs) are handled by the layout, not by the fonts.
+            if c.is_control() {
+                continue;
+            }
+            glyphs.entry(c).or_insert_with(|| {
+                self.families
+                    .keys()
+                    .map(|family| (family.clone(), self.font_for(family, c).map(str::to_owned)))
+                    .collect()
+            });
+        }
+        CoverageReport { glyphs }
+    }
+}
+
+/// Result of [`FontCoverage::check`].
+#[derive(Debug, Clone, Default)]
+pub struct CoverageReport {
+    /// For each character, the font serving it in each family.
+    pub glyphs: BTreeMap<char, BTreeMap<FontFamily, Option<String>>>,
+}
+
+impl CoverageReport {
+    /// The font serving `c` in `family`, if `c` was checked and is available.
+    pub fn font_for(&self, family: &FontFamily, c: char) -> Option<&str> {
+        self.glyphs.get(&c)?.get(family)?.as_deref()
+    }
+
+    /// Characters none of the families can render.
+    pub fn missing(&self) -> Vec<char> {
+        self.glyphs
+            .iter()
+            .filter(|(_, families)| families.values().all(Option::is_none))
+            .map(|(c, _)| *c)
+            .collect()
+    }
+
+    /// Characters `family` can't render.
+    pub fn missing_in(&self, family: &FontFamily) -> Vec<char> {
+        self.glyphs
+            .iter()
+            .filter(|(_, families)| families.get(family).map_or(true, Option::is_none))
+            .map(|(c, _)| *c)
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.missing().is_empty()
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::sync::OnceLock;
 
 use egui::{FontData, FontDefinitions, FontFamily, FontId, FontTweak};
@@ -12,6 +13,21 @@ pub fn font_family_medium() -> FontFamily {
     FF.get_or_init(|| FontFamily::Name("medium".into())).clone()
 }
 
+pub fn font_family_semibold() -> FontFamily {
+    static FF: OnceLock<FontFamily> = OnceLock::new();
+    FF.get_or_init(|| FontFamily::Name("semibold".into())).clone()
+}
+
+pub fn font_family_bold() -> FontFamily {
+    static FF: OnceLock<FontFamily> = OnceLock::new();
+    FF.get_or_init(|| FontFamily::Name("bold".into())).clone()
+}
+
+pub fn font_family_bold_italic() -> FontFamily {
+    static FF: OnceLock<FontFamily> = OnceLock::new();
+    FF.get_or_init(|| FontFamily::Name("bold_italic".into())).clone()
+}
+
 pub fn font_family_icons() -> FontFamily {
     static FF: OnceLock<FontFamily> = OnceLock::new();
     FF.get_or_init(|| FontFamily::Name("icons".into())).clone()
@@ -25,79 +41,413 @@ pub fn font_medium(size: f32) -> FontId {
     FontId::new(size, font_family_medium())
 }
 
+pub fn font_semibold(size: f32) -> FontId {
+    FontId::new(size, font_family_semibold())
+}
+
+pub fn font_bold(size: f32) -> FontId {
+    FontId::new(size, font_family_bold())
+}
+
+pub fn font_bold_italic(size: f32) -> FontId {
+    FontId::new(size, font_family_bold_italic())
+}
+
 pub fn font_icons(size: f32) -> FontId {
     FontId::new(size, font_family_icons())
 }
 
+/// Include a bundled font file, or its subset generated by the build script when the `subset`
+/// feature is enabled.
+#[cfg(not(feature = "subset"))]
+#[allow(unused_macros)] // when no font is enabled
+macro_rules! include_font {
+    ($path:literal) => {
+        include_bytes!($path)
+    };
+}
+
+#[cfg(feature = "subset")]
+#[allow(unused_macros)] // when no font is enabled
+macro_rules! include_font {
+    ($path:literal) => {
+        include_bytes!(concat!(env!("OUT_DIR"), "/", $path))
+    };
+}
+
+/// Name of the Inter Regular font in [`FontDefinitions::font_data`].
+pub const INTER_REGULAR: &str = "Inter-Regular";
+/// Name of the Inter Italic font in [`FontDefinitions::font_data`].
+pub const INTER_ITALIC: &str = "Inter-Italic";
+/// Name of the Inter Medium font in [`FontDefinitions::font_data`].
+pub const INTER_MEDIUM: &str = "Inter-Medium";
+/// Name of the Inter SemiBold font in [`FontDefinitions::font_data`].
+pub const INTER_SEMIBOLD: &str = "Inter-SemiBold";
+/// Name of the Inter Bold font in [`FontDefinitions::font_data`].
+pub const INTER_BOLD: &str = "Inter-Bold";
+/// Name of the Inter Bold Italic font in [`FontDefinitions::font_data`].
+pub const INTER_BOLD_ITALIC: &str = "Inter-BoldItalic";
+/// Name of the Jetbrains Mono font in [`FontDefinitions::font_data`].
+pub const JETBRAINS_MONO: &str = "Jetbrains Mono";
+/// Name of the Phosphor icons font in [`FontDefinitions::font_data`].
+pub const PHOSPHOR: &str = "Phosphor";
+/// Name of the Noto Emoji font in [`FontDefinitions::font_data`].
+pub const NOTO_EMOJI: &str = "NotoEmoji";
+
+/// Tweak of the Inter faces, the reference the other fonts are aligned on.
+pub const INTER_TWEAK: FontTweak = FontTweak {
+    scale: 1.0,
+    y_offset_factor: 0.0,
+    y_offset: 0.0,
+    // Same as egui default, makes the text look more centered in buttons and such
+    baseline_offset_factor: -0.0333,
+};
+
+/// Tweak of Jetbrains Mono, its cap height is already centered like Inter.
+pub const JETBRAINS_MONO_TWEAK: FontTweak = INTER_TWEAK;
+
+/// Tweak of the Phosphor icons, so they are centered on the cap height of Inter.
+///
+/// Phosphor glyphs are centered between the ascent and descent of the font, which puts them about
+/// 7% of the font size above the center of Inter capitals.
+pub const PHOSPHOR_TWEAK: FontTweak = FontTweak {
+    y_offset_factor: 0.072,
+    ..INTER_TWEAK
+};
+
+/// Tweak of Noto Emoji, smaller than the text and centered on the cap height of Inter.
+pub const NOTO_EMOJI_TWEAK: FontTweak = FontTweak {
+    scale: 0.8, // make it smaller
+    y_offset_factor: -0.025,
+    ..INTER_TWEAK
+};
+
+/// Font definitions with all the fonts bundled in this crate.
+///
+/// Each font is gated behind its own crate feature (`font_inter_regular`, `font_inter_italic`,
+/// `font_inter_medium`, `font_inter_semibold`, `font_inter_bold`, `font_inter_bold_italic`,
+/// `font_jetbrains_mono`, `font_phosphor` and `font_noto_emoji`). All the font families are
+/// always defined, a family whose main face is disabled falls back to the closest enabled one
+/// (e.g. bold → semibold → medium → regular).
+///
+/// With the `subset` feature, the Inter, Jetbrains Mono and Phosphor fonts are reduced at build
+/// time to the characters configured in `build.rs`.
 pub fn fonts() -> FontDefinitions {
+    #[allow(unused_mut)]
     let mut fonts = FontDefinitions::empty();
+    #[cfg(feature = "font_inter_regular")]
+    fonts.font_data.insert(
+        INTER_REGULAR.to_owned(),
+        FontData::from_static(include_font!("inter/Inter-Regular.ttf")).tweak(INTER_TWEAK),
+    );
+    #[cfg(feature = "font_inter_italic")]
     fonts.font_data.insert(
-        "Inter-Regular".to_string(),
-        FontData::from_static(include_bytes!("inter/Inter-Regular.ttf")),
+        INTER_ITALIC.to_owned(),
+        FontData::from_static(include_font!("inter/Inter-Italic.ttf")).tweak(INTER_TWEAK),
     );
+    #[cfg(feature = "font_inter_medium")]
     fonts.font_data.insert(
-        "Inter-Italic".to_string(),
-        FontData::from_static(include_bytes!("inter/Inter-Italic.ttf")),
+        INTER_MEDIUM.to_owned(),
+        FontData::from_static(include_font!("inter/Inter-Medium.ttf")).tweak(INTER_TWEAK),
     );
+    #[cfg(feature = "font_inter_semibold")]
     fonts.font_data.insert(
-        "Inter-Medium".to_string(),
-        FontData::from_static(include_bytes!("inter/Inter-Medium.ttf")),
+        INTER_SEMIBOLD.to_owned(),
+        FontData::from_static(include_font!("inter/Inter-SemiBold.ttf")).tweak(INTER_TWEAK),
     );
+    #[cfg(feature = "font_inter_bold")]
     fonts.font_data.insert(
-        "Jetbrains Mono".to_string(),
-        FontData::from_static(include_bytes!("jetbrainsmono/JetBrainsMonoNL-Regular.ttf")),
+        INTER_BOLD.to_owned(),
+        FontData::from_static(include_font!("inter/Inter-Bold.ttf")).tweak(INTER_TWEAK),
     );
+    #[cfg(feature = "font_inter_bold_italic")]
     fonts.font_data.insert(
-        "Phosphor".to_string(),
-        FontData::from_static(include_bytes!("phosphor/Phosphor.ttf")),
+        INTER_BOLD_ITALIC.to_owned(),
+        FontData::from_static(include_font!("inter/Inter-BoldItalic.ttf")).tweak(INTER_TWEAK),
     );
+    #[cfg(feature = "font_jetbrains_mono")]
     fonts.font_data.insert(
-        "NotoEmoji".to_string(),
-        FontData::from_static(include_bytes!("noto/NotoEmoji-Regular.ttf")).tweak(
-            FontTweak {
-                scale: 0.8, // make it smaller
-                ..Default::default()
-            },
-        ),
+        JETBRAINS_MONO.to_owned(),
+        FontData::from_static(include_font!("jetbrainsmono/JetBrainsMonoNL-Regular.ttf"))
+            .tweak(JETBRAINS_MONO_TWEAK),
+    );
+    #[cfg(feature = "font_phosphor")]
+    fonts.font_data.insert(
+        PHOSPHOR.to_owned(),
+        FontData::from_static(include_font!("phosphor/Phosphor.ttf")).tweak(PHOSPHOR_TWEAK),
+    );
+    #[cfg(feature = "font_noto_emoji")]
+    fonts.font_data.insert(
+        NOTO_EMOJI.to_owned(),
+        FontData::from_static(include_bytes!("noto/NotoEmoji-Regular.ttf")).tweak(NOTO_EMOJI_TWEAK),
     );
 
     // Font families
-    fonts.families.insert(
+    let fallbacks = [NOTO_EMOJI, PHOSPHOR];
+    insert_family(
+        &mut fonts,
         FontFamily::Proportional,
-        vec![
-            "Inter-Regular".to_owned(),
-            "NotoEmoji".to_owned(),
-            "Phosphor".to_owned(),
-        ],
+        &[INTER_REGULAR, INTER_MEDIUM, INTER_ITALIC],
+        &fallbacks,
     );
-    fonts.families.insert(
+    insert_family(
+        &mut fonts,
         font_family_italic(),
-        vec![
-            "Inter-Italic".to_owned(),
-            "NotoEmoji".to_owned(),
-            "Phosphor".to_owned(),
-        ],
+        &[INTER_ITALIC, INTER_REGULAR, INTER_MEDIUM],
+        &fallbacks,
     );
-    fonts.families.insert(
+    insert_family(
+        &mut fonts,
         font_family_medium(),
-        vec![
-            "Inter-Medium".to_owned(),
-            "NotoEmoji".to_owned(),
-            "Phosphor".to_owned(),
-        ],
+        &[INTER_MEDIUM, INTER_REGULAR, INTER_ITALIC],
+        &fallbacks,
     );
-    fonts.families.insert(
-        FontFamily::Monospace,
-        vec![
-            "Jetbrains Mono".to_owned(),
-            "NotoEmoji".to_owned(),
-            "Phosphor".to_owned(),
+    insert_family(
+        &mut fonts,
+        font_family_semibold(),
+        &[INTER_SEMIBOLD, INTER_MEDIUM, INTER_REGULAR, INTER_ITALIC],
+        &fallbacks,
+    );
+    insert_family(
+        &mut fonts,
+        font_family_bold(),
+        &[INTER_BOLD, INTER_SEMIBOLD, INTER_MEDIUM, INTER_REGULAR, INTER_ITALIC],
+        &fallbacks,
+    );
+    insert_family(
+        &mut fonts,
+        font_family_bold_italic(),
+        &[
+            INTER_BOLD_ITALIC,
+            INTER_ITALIC,
+            INTER_BOLD,
+            INTER_SEMIBOLD,
+            INTER_MEDIUM,
+            INTER_REGULAR,
         ],
+        &fallbacks,
     );
-    fonts.families.insert(
-        font_family_icons(),
-        vec!["Phosphor".to_owned()],
+    insert_family(
+        &mut fonts,
+        FontFamily::Monospace,
+        &[JETBRAINS_MONO, INTER_REGULAR, INTER_MEDIUM, INTER_ITALIC],
+        &fallbacks,
     );
+    insert_family(&mut fonts, font_family_icons(), &[PHOSPHOR], &[]);
 
     fonts
 }
+
+/// Define a font family with the first available font of `faces`, followed by the available
+/// `fallbacks`.
+fn insert_family(
+    fonts: &mut FontDefinitions,
+    family: FontFamily,
+    faces: &[&str],
+    fallbacks: &[&str],
+) {
+    let face = faces.iter().find(|name| fonts.font_data.contains_key(**name));
+    let names = face
+        .into_iter()
+        .chain(fallbacks)
+        .filter(|name| fonts.font_data.contains_key(**name))
+        .map(|name| name.to_string())
+        .collect();
+    fonts.families.insert(family, names);
+}
+
+/// Where a font is inserted in the list of fonts of a family.
+///
+/// Fonts earlier in the list have priority, later ones are only used as fallbacks for the
+/// characters missing from the previous ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontPosition {
+    /// Before any other font of the family.
+    First,
+
+    /// After the main font of the family (Inter or Jetbrains Mono), but before the emoji and
+    /// icons fallbacks. This is the right place for fonts covering other scripts.
+    ///
+    /// The main font stays the same when other fonts are inserted [`FontPosition::First`].
+    AfterPrimary,
+
+    /// After all the other fonts of the family.
+    Last,
+
+    /// Just before the given font, or last if it's not part of the family.
+    Before(String),
+
+    /// Just after the given font, or last if it's not part of the family.
+    After(String),
+}
+
+/// Builder for [`FontDefinitions`] starting from the fonts bundled with this crate, to which
+/// application fonts can be added.
+///
+/// ```
+/// # use egui::{FontData, FontFamily, FontTweak};
+/// # use egui_ui_refresh::fonts::{font_family_medium, FontPosition, FontsBuilder, text_families};
+/// # let noto_sans_jp: &'static [u8] = &[];
+/// # let brand_font: &'static [u8] = &[];
+/// let fonts = FontsBuilder::new()
+///     .add_font(
+///         "NotoSansJP",
+///         FontData::from_static(noto_sans_jp),
+///         &text_families(),
+///         FontPosition::AfterPrimary,
+///     )
+///     .add_font(
+///         "Brand",
+///         FontData::from_static(brand_font).tweak(FontTweak {
+///             y_offset_factor: 0.1,
+///             ..Default::default()
+///         }),
+///         &[font_family_medium()],
+///         FontPosition::First,
+///     )
+///     .build();
+/// assert_eq!(fonts.families[&font_family_medium()][0], "Brand");
+/// ```
+pub struct FontsBuilder {
+    definitions: FontDefinitions,
+
+    /// Main font of each family, after which [`FontPosition::AfterPrimary`] inserts.
+    primary: BTreeMap<FontFamily, String>,
+}
+
+impl Default for FontsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<FontDefinitions> for FontsBuilder {
+    /// The main font of each family is its first font that isn't an emoji or icons fallback.
+    fn from(definitions: FontDefinitions) -> Self {
+        let primary = definitions
+            .families
+            .iter()
+            .filter_map(|(family, fonts)| {
+                let font = fonts.iter().find(|font| !is_bundled_fallback(font))?;
+                Some((family.clone(), font.clone()))
+            })
+            .collect();
+        Self {
+            definitions,
+            primary,
+        }
+    }
+}
+
+impl FontsBuilder {
+    /// Start from the fonts bundled with this crate, see [`fonts`].
+    pub fn new() -> Self {
+        Self::from(fonts())
+    }
+
+    /// Add a font to the given families.
+    ///
+    /// A font with the same name is replaced.
+    #[inline]
+    pub fn add_font(
+        self,
+        name: impl Into<String>,
+        data: FontData,
+        families: &[FontFamily],
+        position: FontPosition,
+    ) -> Self {
+        let name = name.into();
+        let mut slf = self.add_font_data(name.clone(), data);
+        for family in families {
+            slf = slf.add_to_family(family.clone(), name.clone(), position.clone());
+        }
+        slf
+    }
+
+    /// Register a font without adding it to any family.
+    #[inline]
+    pub fn add_font_data(mut self, name: impl Into<String>, data: FontData) -> Self {
+        self.definitions.font_data.insert(name.into(), data);
+        self
+    }
+
+    /// Add an already registered font to a family, creating the family if needed.
+    ///
+    /// If the font is already part of the family, it is moved to the new position.
+    pub fn add_to_family(
+        mut self,
+        family: FontFamily,
+        name: impl Into<String>,
+        position: FontPosition,
+    ) -> Self {
+        let name = name.into();
+        let primary = self.primary.get(&family);
+        let fonts = self.definitions.families.entry(family).or_default();
+        fonts.retain(|font| *font != name);
+
+        let index = match position {
+            FontPosition::First => 0,
+            FontPosition::AfterPrimary => fonts
+                .iter()
+                .position(|font| Some(font) == primary)
+                .or_else(|| fonts.iter().position(|font| !is_bundled_fallback(font)))
+                .map_or(0, |index| index + 1),
+            FontPosition::Last => fonts.len(),
+            FontPosition::Before(other) => fonts
+                .iter()
+                .position(|font| *font == other)
+                .unwrap_or(fonts.len()),
+            FontPosition::After(other) => fonts
+                .iter()
+                .position(|font| *font == other)
+                .map_or(fonts.len(), |index| index + 1),
+        };
+        fonts.insert(index, name);
+        self
+    }
+
+    /// Remove a font from a family, the font data is kept.
+    #[inline]
+    pub fn remove_from_family(mut self, family: &FontFamily, name: &str) -> Self {
+        if let Some(fonts) = self.definitions.families.get_mut(family) {
+            fonts.retain(|font| font != name);
+        }
+        self
+    }
+
+    /// Change the [`FontTweak`] of a registered font, including the bundled ones.
+    #[inline]
+    pub fn tweak(mut self, name: &str, tweak: FontTweak) -> Self {
+        if let Some(data) = self.definitions.font_data.get_mut(name) {
+            data.tweak = tweak;
+        }
+        self
+    }
+
+    /// The font definitions built so far.
+    pub fn definitions(&self) -> &FontDefinitions {
+        &self.definitions
+    }
+
+    pub fn build(self) -> FontDefinitions {
+        self.definitions
+    }
+}
+
+/// The families using text fonts: proportional, italic, medium, semibold, bold, bold italic and
+/// monospace.
+pub fn text_families() -> [FontFamily; 7] {
+    [
+        FontFamily::Proportional,
+        font_family_italic(),
+        font_family_medium(),
+        font_family_semibold(),
+        font_family_bold(),
+        font_family_bold_italic(),
+        FontFamily::Monospace,
+    ]
+}
+
+fn is_bundled_fallback(font: &str) -> bool {
+    font == NOTO_EMOJI || font == PHOSPHOR
+}
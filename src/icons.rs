@@ -1,14 +1,160 @@
-use egui::RichText;
+use egui::{Color32, FontId, Painter, Rect, Response, RichText, Sense, Ui, Vec2, Widget};
+use egui::WidgetText;
 
 use crate::fonts::font_icons;
+use crate::RefreshedTheme;
 
-pub const CLOSE: &'static str = "\u{E4F6}";
-pub const INFO: &'static str = "\u{E2CE}";
-pub const WARNING: &'static str = "\u{E4E0}";
-pub const WARNING_CIRCLE: &'static str = "\u{E4E2}";
-pub const CHECK: &'static str = "\u{E182}";
-pub const EXCLAMATION_MARK: &'static str = "\u{EE44}";
+pub const CLOSE: Icon = Icon::new('\u{E4F6}');
+pub const INFO: Icon = Icon::new('\u{E2CE}');
+pub const WARNING: Icon = Icon::new('\u{E4E0}');
+pub const WARNING_CIRCLE: Icon = Icon::new('\u{E4E2}');
+pub const CHECK: Icon = Icon::new('\u{E182}');
+pub const EXCLAMATION_MARK: Icon = Icon::new('\u{EE44}');
 
-pub fn icon(icon: impl Into<String>, size: f32) -> RichText {
-    RichText::new(icon).font(font_icons(size)).line_height(Some(size - 1.0))
+/// Size used by [`Icon::new`] when none is specified.
+pub const DEFAULT_ICON_SIZE: f32 = 16.0;
+
+/// A Phosphor icon codepoint along with the size it should be displayed at.
+///
+/// An [`Icon`] can be used in three ways:
+/// - as text, with [`Icon::rich_text`] (or any API taking a [`WidgetText`]),
+/// - as an image-like widget with [`Icon::image`], which is centered on its glyph bounds rather
+///   than on the font line height,
+/// - as a borderless button tinted according to the interaction state with [`Icon::button`].
+///
+/// ```no_run
+/// # use egui_ui_refresh::icons;
+/// # egui::__run_test_ui(|ui| {
+/// ui.label(icons::INFO.rich_text());
+/// ui.add(icons::WARNING.with_size(24.0).image().tint(egui::Color32::YELLOW));
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Icon {
+    /// Codepoint of the glyph in the icons font.
+    pub codepoint: char,
+
+    /// Default display size, in points.
+    pub size: f32,
+}
+
+impl Icon {
+    pub const fn new(codepoint: char) -> Self {
+        Self {
+            codepoint,
+            size: DEFAULT_ICON_SIZE,
+        }
+    }
+
+    /// Same icon, displayed at another size.
+    #[inline]
+    pub const fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn font_id(&self) -> FontId {
+        font_icons(self.size)
+    }
+
+    /// The icon as text, to be mixed with other labels.
+    pub fn rich_text(&self) -> RichText {
+        RichText::new(self.codepoint.to_string()).font(self.font_id())
+    }
+
+    /// The icon as an image-like widget.
+    pub fn image(&self) -> IconImage {
+        IconImage {
+            icon: *self,
+            tint: None,
+        }
+    }
+
+    /// The icon as a button without any background.
+    pub fn button(&self) -> IconButton {
+        IconButton { icon: *self }
+    }
+
+    /// Paint the icon so that its visible bounds are centered in `rect`.
+    pub fn paint(&self, painter: &Painter, rect: Rect, color: Color32) {
+        let galley = painter.layout_no_wrap(self.codepoint.to_string(), self.font_id(), color);
+
+        // Center on the actual glyph bounds, the logical rect includes the font ascent and descent.
+        let ink_rect = galley
+            .rows
+            .first()
+            .and_then(|row| row.glyphs.first())
+            .filter(|glyph| !glyph.uv_rect.is_nothing())
+            .map(|glyph| Rect::from_min_size(glyph.pos + glyph.uv_rect.offset, glyph.uv_rect.size))
+            .unwrap_or(galley.rect);
+
+        let pos = rect.center() - ink_rect.center().to_vec2();
+        painter.galley(painter.round_pos_to_pixels(pos), galley, color);
+    }
+}
+
+impl From<Icon> for RichText {
+    fn from(icon: Icon) -> Self {
+        icon.rich_text()
+    }
+}
+
+impl From<Icon> for WidgetText {
+    fn from(icon: Icon) -> Self {
+        icon.rich_text().into()
+    }
+}
+
+/// An [`Icon`] displayed as an image, see [`Icon::image`].
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub struct IconImage {
+    icon: Icon,
+    tint: Option<Color32>,
+}
+
+impl IconImage {
+    /// Color of the icon, defaults to the text color.
+    #[inline]
+    pub fn tint(mut self, tint: impl Into<Color32>) -> Self {
+        self.tint = Some(tint.into());
+        self
+    }
+}
+
+impl Widget for IconImage {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(self.icon.size), Sense::hover());
+        if ui.is_rect_visible(rect) {
+            let color = self.tint.unwrap_or_else(|| ui.visuals().text_color());
+            self.icon.paint(ui.painter(), rect, color);
+        }
+        response
+    }
+}
+
+/// An [`Icon`] displayed as a button, see [`Icon::button`].
+///
+/// The icon is tinted with [`RefreshedTheme::inactive`], [`RefreshedTheme::hovered`] and
+/// [`RefreshedTheme::active`] depending on the interaction state.
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub struct IconButton {
+    icon: Icon,
+}
+
+impl Widget for IconButton {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(self.icon.size), Sense::click());
+        if ui.is_rect_visible(rect) {
+            let theme = RefreshedTheme::get();
+            let color = if response.is_pointer_button_down_on() || response.has_focus() {
+                theme.active
+            } else if response.hovered() {
+                theme.hovered
+            } else {
+                theme.inactive
+            };
+            self.icon.paint(ui.painter(), rect, color);
+        }
+        response
+    }
 }
@@ -1,9 +1,14 @@
 use std::sync::OnceLock;
 
-use egui::{Color32, Context, NumExt, Rounding, Stroke, Style, Visuals};
-use egui::{Frame, Margin, TextStyle, Vec2};
+use egui::{Color32, Context, FontDefinitions, FontFamily, Rounding, Stroke, Style, Visuals};
+use egui::{FontId, Frame, Margin, TextStyle, Vec2};
 use egui::epaint::Shadow;
-use egui::style::NumberFormatter;
+use egui::RichText;
+
+use crate::number_format::NumberFormat;
+use crate::time_format::TimeFormat;
+use crate::typography::{TypeScale, Typography};
+use crate::units::{Unit, UnitFormat};
 
 // pub mod rerun;
 pub mod top_bar;
@@ -11,10 +16,35 @@ pub mod top_bar;
 pub mod toasts;
 #[cfg(feature = "modal")]
 pub mod modal;
+#[cfg(feature = "modal")]
+pub mod dialogs;
+#[cfg(feature = "modal")]
+pub mod async_modal;
 /// A few codepoints from Phosphor icons
 pub mod icons;
 /// New fonts packaged with this crate
 pub mod fonts;
+#[cfg(feature = "color_emoji")]
+pub mod color_emoji;
+/// Missing glyphs diagnostics for font definitions
+pub mod font_coverage;
+/// Fallbacks from the fonts installed on the system
+#[cfg(feature = "system_fonts")]
+pub mod system_fonts;
+/// Configurable formatting and parsing of numbers
+pub mod number_format;
+/// Formatting and parsing of values with units
+pub mod units;
+/// Formatting of durations, relative times and timestamps
+pub mod time_format;
+/// Named text styles for a consistent typography
+pub mod typography;
+
+/// Colors of the info, warning, error and success messages, in toasts and dialogs.
+pub const INFO_COLOR: Color32 = Color32::from_rgb(0, 155, 255);
+pub const WARNING_COLOR: Color32 = Color32::from_rgb(255, 212, 0);
+pub const ERROR_COLOR: Color32 = Color32::from_rgb(255, 32, 0);
+pub const SUCCESS_COLOR: Color32 = Color32::from_rgb(0, 255, 32);
 
 static REFRESHED_THEME: OnceLock<RefreshedTheme> = OnceLock::new();
 
@@ -30,10 +60,17 @@ pub struct RefreshedTheme {
     pub panel_bg_color: Color32,
     pub selection: Color32,
     pub inactive: Color32,
+    pub hovered: Color32,
     pub noninteractive: Color32,
     pub active: Color32,
     pub normal_text_size: f32,
     pub heading_text_size: f32,
+    pub type_scale: TypeScale,
+    /// Display the numbers of [`egui::DragValue`] and [`egui::Slider`] with fixed advance digits,
+    /// in the monospace font. Off by default.
+    pub tabular_numbers: bool,
+    /// Format of the numbers of [`egui::DragValue`] and [`egui::Slider`].
+    pub number_format: NumberFormat,
     pub view_padding: f32,
     pub window_rounding: f32,
     pub normal_rounding: f32,
@@ -51,11 +88,15 @@ impl RefreshedTheme {
         Self {
             normal_text_size: 12.0,
             heading_text_size: 16.0,
+            type_scale: TypeScale::new(),
+            tabular_numbers: false,
+            number_format: NumberFormat::DEFAULT,
             bottom_bar_color: Color32::from_rgb(0x14, 0x18, 0x19),
             tab_bar_color: Color32::from_rgb(0x18, 0x1c, 0x1e),
             panel_bg_color: Color32::from_rgb(0x0d, 0x10, 0x11),
             selection: Color32::from_rgb(0x00, 0x3d, 0xa1),
             inactive: Color32::from_rgb(0xca, 0xd8, 0xde),
+            hovered: Color32::from_rgb(0xe6, 0xec, 0xef),
             noninteractive: Color32::from_rgb(0x7d, 0x8c, 0x92),
             active: Color32::WHITE,
             expansion: 2.0,
@@ -90,7 +131,7 @@ impl Default for RefreshedTheme {
 
 impl RefreshedTheme {
     pub fn init_default() -> &'static Self {
-        REFRESHED_THEME.get_or_init(|| RefreshedTheme::new())
+        REFRESHED_THEME.get_or_init(RefreshedTheme::new)
     }
 
     pub fn init(self) -> &'static Self {
@@ -102,10 +143,47 @@ impl RefreshedTheme {
     }
 
     /// Apply style to the given egui context.
+    ///
+    /// Headings use the semibold font family when the fonts of the context define it, and the
+    /// proportional family otherwise, as do the styles of [`Self::type_scale`]. Fonts set with [`Context::set_fonts`] are only loaded at the
+    /// start of the next frame, so use [`Self::apply_with_fonts`] to set them along with the style.
     pub fn apply(&self, ctx: &Context) {
+        // Fonts are not available before the first frame, egui always defines these two
+        let families = if ctx.frame_nr() == 0 {
+            vec![FontFamily::Proportional, FontFamily::Monospace]
+        } else {
+            ctx.fonts(|fonts| fonts.families())
+        };
+        self.apply_style(ctx, &families);
+    }
+
+    /// Set the fonts and apply style to the given egui context, see [`Self::apply`].
+    ///
+    /// ```no_run
+    /// # use egui_ui_refresh::{fonts, RefreshedTheme};
+    /// # let ctx = egui::Context::default();
+    /// RefreshedTheme::init_default().apply_with_fonts(&ctx, fonts::fonts());
+    /// ```
+    pub fn apply_with_fonts(&self, ctx: &Context, fonts: FontDefinitions) {
+        let families: Vec<_> = fonts.families.keys().cloned().collect();
+        ctx.set_fonts(fonts);
+        self.apply_style(ctx, &families);
+    }
+
+    /// Apply style, using the font families of `families` and the proportional family in place
+    /// of the others.
+    fn apply_style(&self, ctx: &Context, families: &[FontFamily]) {
+        let family = |family: FontFamily| {
+            if families.contains(&family) {
+                family
+            } else {
+                FontFamily::Proportional
+            }
+        };
+
         let mut egui_style = Style {
             visuals: Visuals::dark(),
-            number_formatter: NumberFormatter::new(format_with_decimals_in_range),
+            number_formatter: self.number_format.number_formatter(),
             ..Default::default()
         };
 
@@ -113,11 +191,23 @@ impl RefreshedTheme {
             egui_style.text_styles.get_mut(&text_style).unwrap().size = self.normal_text_size;
         }
 
-        egui_style
-            .text_styles
-            .get_mut(&TextStyle::Heading)
-            .unwrap()
-            .size = self.heading_text_size;
+        egui_style.text_styles.insert(
+            TextStyle::Heading,
+            FontId::new(self.heading_text_size, family(fonts::font_family_semibold())),
+        );
+
+        for typography in Typography::ALL {
+            let font_id = self.type_scale.get(typography).font_id();
+            egui_style.text_styles.insert(
+                typography.text_style(),
+                FontId::new(font_id.size, family(font_id.family)),
+            );
+        }
+
+        if self.tabular_numbers {
+            // Inter digits are proportional, values jitter while dragging
+            egui_style.drag_value_text_style = TextStyle::Monospace;
+        }
 
         // We want labels and buttons to have the same height.
         // Intuitively, we would just assign font_size to
@@ -170,6 +260,7 @@ impl RefreshedTheme {
         egui_style.visuals.widgets.noninteractive.bg_stroke.color = Color32::from_gray(30); // separator lines, panel lines, etc
 
         egui_style.visuals.widgets.inactive.fg_stroke.color = self.inactive; // button text
+        egui_style.visuals.widgets.hovered.fg_stroke.color = self.hovered; // hovered button text
         egui_style.visuals.widgets.noninteractive.fg_stroke.color = self.noninteractive; // non-interactive text
         egui_style.visuals.widgets.active.fg_stroke.color = self.active; // strong text and active button text
 
@@ -229,6 +320,25 @@ impl RefreshedTheme {
         ctx.set_style(egui_style);
     }
 
+    /// Text using one of the [`Typography`] styles, with its line height and letter spacing.
+    pub fn rich_text(&self, typography: Typography, text: impl Into<String>) -> RichText {
+        let style = self.type_scale.get(typography);
+        RichText::new(text)
+            .text_style(typography.text_style())
+            .line_height(Some(style.line_height))
+            .extra_letter_spacing(style.letter_spacing)
+    }
+
+    /// Format of values with `unit`, using the number format of the theme.
+    pub fn unit_format(&self, unit: Unit) -> UnitFormat {
+        UnitFormat::new(unit).with_number_format(self.number_format)
+    }
+
+    /// Format of durations and times, using the number format of the theme.
+    pub fn time_format(&self) -> TimeFormat {
+        TimeFormat::new().with_number_format(self.number_format)
+    }
+
     pub fn panel_margin(&self) -> Margin {
         Margin::symmetric(self.view_padding, 0.0)
     }
@@ -274,44 +384,3 @@ impl RefreshedTheme {
         Vec2::splat(14.0)
     }
 }
-
-fn format_with_decimals_in_range(
-    value: f64,
-    decimal_range: std::ops::RangeInclusive<usize>,
-) -> String {
-    fn format_with_decimals(value: f64, decimals: usize) -> String {
-        re_format::FloatFormatOptions::DEFAULT_f64
-            .with_decimals(decimals)
-            .with_strip_trailing_zeros(false)
-            .format(value)
-    }
-
-    let epsilon = 16.0 * f32::EPSILON; // margin large enough to handle most peoples round-tripping needs
-
-    let min_decimals = *decimal_range.start();
-    let max_decimals = *decimal_range.end();
-    debug_assert!(min_decimals <= max_decimals);
-    debug_assert!(max_decimals < 100);
-    let max_decimals = max_decimals.at_most(16);
-    let min_decimals = min_decimals.at_most(max_decimals);
-
-    if min_decimals < max_decimals {
-        // Try using a few decimals as possible, and then add more until we have enough precision
-        // to round-trip the number.
-        for decimals in min_decimals..max_decimals {
-            let text = format_with_decimals(value, decimals);
-            if let Some(parsed) = re_format::parse_f64(&text) {
-                if egui::emath::almost_equal(parsed as f32, value as f32, epsilon) {
-                    // Enough precision to show the value accurately - good!
-                    return text;
-                }
-            }
-        }
-        // The value has more precision than we expected.
-        // Probably the value was set not by the slider, but from outside.
-        // In any case: show the full value
-    }
-
-    // Use max decimals
-    format_with_decimals(value, max_decimals)
-}
@@ -1,7 +1,7 @@
 use egui::{NumExt, Ui};
 
 use crate::{icons, RefreshedTheme};
-use crate::icons::icon;
+use crate::fonts::font_family_semibold;
 
 /// Helper object to handle a [`Modal`] window.
 ///
@@ -23,34 +23,180 @@ use crate::icons::icon;
 /// });
 /// # });
 /// ```
-#[derive(Default)]
-pub struct ModalHandler {
-    modal: Option<Modal>,
-    should_open: bool,
+///
+/// Modals shown while another one is open are stacked on top of it: each one dims those below,
+/// and only the topmost one reacts to Escape and outside clicks. A child modal is opened from
+/// the content of its parent, and must be shown after it:
+/// ```
+/// # use egui_ui_refresh::modal::{Modal, ModalHandler};
+/// let mut edit_modal = ModalHandler::default();
+/// let mut discard_modal = ModalHandler::default();
+///
+/// # egui::__run_test_ui(|ui| {
+/// edit_modal.ui(ui.ctx(), || Modal::new("Edit"), |ui, open| {
+///     if ui.button("Discard").clicked() {
+///         discard_modal.open();
+///     }
+///
+///     discard_modal.ui(ui.ctx(), || Modal::new("Discard changes?"), |ui, discard_open| {
+///         if ui.button("Discard").clicked() {
+///             *discard_open = false;
+///             *open = false;
+///         }
+///     });
+/// });
+/// # });
+/// ```
+///
+/// A `ModalHandler<T, R>` also passes a payload of type `T` to the content, which completes the
+/// modal with a result of type `R`, see [`ModalHandler::show`].
+pub struct ModalHandler<T = (), R = ()> {
+    /// The modal with its payload, while open or closing.
+    modal: Option<(Modal, T)>,
+
+    /// The payload of the modal to open next time it is shown.
+    pending: Option<T>,
+
+    result: Option<R>,
 }
 
-impl ModalHandler {
-    /// Open the model next time the [`ModalHandler::ui`] method is called.
+impl<T, R> Default for ModalHandler<T, R> {
+    fn default() -> Self {
+        Self {
+            modal: None,
+            pending: None,
+            result: None,
+        }
+    }
+}
+
+impl<R> ModalHandler<(), R> {
+    /// Open the model next time it is shown.
     pub fn open(&mut self) {
-        self.should_open = true;
+        self.open_with(());
     }
+}
 
+impl ModalHandler {
     /// Draw the modal window, creating/destroying it as required.
-    pub fn ui<R>(
+    ///
+    /// The modal is kept until its close animation is over, `content_ui` being called to draw it
+    /// with its result ignored.
+    pub fn ui<X>(
         &mut self,
         ctx: &egui::Context,
         make_modal: impl FnOnce() -> Modal,
-        content_ui: impl FnOnce(&mut egui::Ui, &mut bool) -> R,
-    ) -> Option<R> {
-        if self.modal.is_none() && self.should_open {
-            self.modal = Some(make_modal());
-            self.should_open = false;
+        content_ui: impl FnOnce(&mut egui::Ui, &mut bool) -> X,
+    ) -> Option<X> {
+        self.show_modal(ctx, |_| make_modal(), |ui, open, _| content_ui(ui, open))
+    }
+}
+
+impl<T, R> ModalHandler<T, R> {
+    /// Open the modal next time it is shown, with `payload` passed to the content of
+    /// [`ModalHandler::show`] (e.g. the item to delete).
+    ///
+    /// A modal opened again replaces its payload once it closes.
+    pub fn open_with(&mut self, payload: T) {
+        self.pending = Some(payload);
+    }
+
+    /// Whether the modal is open, or will open next time it is shown.
+    ///
+    /// A modal playing its close animation is not open anymore, see [`Self::is_visible`].
+    pub fn is_open(&self) -> bool {
+        self.modal.as_ref().is_some_and(|(modal, _)| !modal.closing) || self.pending.is_some()
+    }
+
+    /// Whether the modal is open, or still shown while its close animation plays.
+    pub fn is_visible(&self) -> bool {
+        self.modal.is_some() || self.pending.is_some()
+    }
+
+    /// The result the content of [`ModalHandler::show`] completed the modal with, returned
+    /// once.
+    ///
+    /// `None` while the modal is open, and when it was dismissed.
+    pub fn take_result(&mut self) -> Option<R> {
+        self.result.take()
+    }
+
+    /// Complete the modal with `result` if it closed without one, e.g. when dismissed.
+    pub(crate) fn complete_if_closed(&mut self, was_open: bool, result: impl FnOnce() -> R) {
+        if was_open && !self.is_open() && self.result.is_none() {
+            self.result = Some(result());
+        }
+    }
+
+    /// Draw the modal window with the payload given to [`ModalHandler::open_with`], until
+    /// `content_ui` completes it with a result.
+    ///
+    /// The result is then available from [`ModalHandler::take_result`]:
+    ///
+    /// ```
+    /// # use egui_ui_refresh::modal::{Modal, ModalHandler};
+    /// let mut rename_modal = ModalHandler::<String, String>::default();
+    ///
+    /// # egui::__run_test_ui(|ui| {
+    /// # let file = String::from("report.pdf");
+    /// if ui.button("Rename").clicked() {
+    ///     rename_modal.open_with(file.clone());
+    /// }
+    ///
+    /// rename_modal.show(ui.ctx(), |file| Modal::new(&format!("Rename {file}")), |ui, name| {
+    ///     ui.text_edit_singleline(name);
+    ///     ui.button("Rename").clicked().then(|| name.clone())
+    /// });
+    /// if let Some(new_name) = rename_modal.take_result() {
+    ///     // rename the file
+    /// }
+    /// # });
+    /// ```
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        make_modal: impl FnOnce(&T) -> Modal,
+        content_ui: impl FnOnce(&mut egui::Ui, &mut T) -> Option<R>,
+    ) {
+        let result = self.show_modal(ctx, make_modal, |ui, open, payload| {
+            let result = content_ui(ui, payload);
+            if result.is_some() {
+                *open = false;
+            }
+            result
+        });
+        if let Some(result) = result.flatten() {
+            self.result = Some(result);
+        }
+    }
+
+    fn show_modal<X>(
+        &mut self,
+        ctx: &egui::Context,
+        make_modal: impl FnOnce(&T) -> Modal,
+        content_ui: impl FnOnce(&mut egui::Ui, &mut bool, &mut T) -> X,
+    ) -> Option<X> {
+        let can_open = self
+            .modal
+            .as_ref()
+            .map_or(true, |(modal, _)| modal.closing);
+        if can_open {
+            if let Some(payload) = self.pending.take() {
+                let mut modal = make_modal(&payload);
+                // Reopening during the close animation plays it backwards
+                if let Some((closing, _)) = &self.modal {
+                    modal.progress = closing.progress;
+                }
+                self.modal = Some((modal, payload));
+                self.result = None;
+            }
         }
 
-        if let Some(modal) = &mut self.modal {
-            let ModalResponse { inner, open } = modal.ui(ctx, content_ui);
+        if let Some((modal, payload)) = &mut self.modal {
+            let ModalResponse { inner, visible, .. } =
+                modal.ui(ctx, |ui, open| content_ui(ui, open, payload));
 
-            if !open {
+            if !visible {
                 self.modal = None;
             }
 
@@ -68,14 +214,146 @@ pub struct ModalResponse<R> {
 
     /// Whether the modal should remain open.
     pub open: bool,
+
+    /// Whether the modal is still shown, while its close animation plays after being closed.
+    ///
+    /// The modal can be dropped once this is `false`.
+    pub visible: bool,
+}
+
+/// How a [`Modal`] animates when opening and closing, see [`Modal::animation`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModalAnimation {
+    /// Duration of the open and close animations, in seconds.
+    pub duration: f32,
+
+    /// Fade the window and the dimmed background in and out.
+    pub fade: bool,
+
+    /// Scale of the window when closed, it grows to `1.0` when opening.
+    pub scale: f32,
+}
+
+impl ModalAnimation {
+    /// A short fade with a slight zoom, the default.
+    pub const DEFAULT: Self = Self {
+        duration: 0.15,
+        fade: true,
+        scale: 0.95,
+    };
+
+    /// Show and hide the modal instantly.
+    pub const NONE: Self = Self {
+        duration: 0.0,
+        fade: false,
+        scale: 1.0,
+    };
+}
+
+impl Default for ModalAnimation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Where a [`Modal`] is shown, see [`Modal::position`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ModalPosition {
+    /// Centered horizontally, near the top of the window, the default.
+    #[default]
+    Top,
+
+    /// Centered in the window.
+    Center,
+
+    /// Below a rect (typically of the widget that opened the modal), or above it if there is
+    /// more room there.
+    Anchored(egui::Rect),
+
+    /// At the bottom of the window, spanning its full width.
+    BottomSheet,
+
+    /// Covering the whole window.
+    FullScreen,
+}
+
+/// How a [`Modal`] can be dismissed by the user, besides the content closing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DismissPolicy {
+    /// Close when pressing Escape.
+    pub escape: bool,
+
+    /// Close when clicking outside the modal.
+    pub outside_click: bool,
+
+    /// Show a close button in the title bar.
+    pub close_button: bool,
+}
+
+impl DismissPolicy {
+    /// Dismissible in every way, the default.
+    pub const ALL: Self = Self {
+        escape: true,
+        outside_click: true,
+        close_button: true,
+    };
+
+    /// Only the content can close the modal.
+    pub const NONE: Self = Self {
+        escape: false,
+        outside_click: false,
+        close_button: false,
+    };
+}
+
+impl Default for DismissPolicy {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Why the user wants to dismiss a [`Modal`], see [`Modal::confirm_close`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DismissReason {
+    Escape,
+    OutsideClick,
+    CloseButton,
 }
 
 /// Show a modal window with Rerun style.
 ///
 /// [`Modal`] fakes as a modal window, since egui [doesn't have them yet](https://github.com/emilk/egui/issues/686).
-/// This done by dimming the background and capturing clicks outside the window.
+/// This done by covering everything underneath with a dimmed layer that captures the pointer, and
+/// by taking the keyboard focus away from the widgets underneath.
+///
+/// When the modal opens, the keyboard focus moves to its content (see [`Modal::initial_focus`]),
+/// Tab and Shift+Tab cycle through the widgets of the content, and the focus goes back to where
+/// it was when the modal closes. egui 0.28 can't move the focus to the next widget, so the first
+/// time the modal opens without an initial focus, no widget has it until Tab is pressed.
+///
+/// By default, the modal is closed with Escape, by clicking outside of it and with the close
+/// button of the title bar, see [`Modal::dismiss_policy`]. A [`Modal::confirm_close`] hook can
+/// veto these, e.g. to keep unsaved changes:
+///
+/// ```
+/// # use std::sync::atomic::{AtomicBool, Ordering};
+/// # use std::sync::Arc;
+/// # use egui_ui_refresh::modal::{DismissPolicy, Modal};
+/// let dirty = Arc::new(AtomicBool::new(false));
+/// let modal = Modal::new("Edit")
+///     .dismiss_policy(DismissPolicy { outside_click: false, ..DismissPolicy::ALL })
+///     .confirm_close({
+///         let dirty = dirty.clone();
+///         move |_reason| !dirty.load(Ordering::Relaxed)
+///     });
+/// ```
+///
+/// Widgets underneath keep the keyboard focus for the frame they are drawn in if they are drawn
+/// before [`Modal::ui`] is called, so call it before the rest of the ui when possible. Only the
+/// widgets underneath are blocked: shortcuts read with [`egui::Context::input`] still fire, so
+/// check [`Modal::is_any_open`] before handling them.
 ///
-/// The positioning of the modal is as follows:
+/// By default, the modal is positioned as follows (see [`Modal::position`] for other placements):
 ///
 /// ```text
 /// ┌─window───────────▲─────────────────────┐
@@ -99,14 +377,45 @@ pub struct ModalResponse<R> {
 /// separator that spans the full width of the modal instead of the usual [`egui::Ui::separator`]
 /// method.
 ///
+/// The modal fades and zooms in when first shown, see [`Modal::animation`]. Once closed, it must
+/// be shown until [`ModalResponse::visible`] is `false` to play the close animation.
+///
 /// Note that [`Modal`] are typically used via the [`ModalHandler`] helper object to reduce
 /// boilerplate.
 pub struct Modal {
     title: String,
+
+    /// Identifies the modal in the stack, its window and its focus trap.
+    id: egui::Id,
+
     min_width: Option<f32>,
     min_height: Option<f32>,
     default_height: Option<f32>,
+    max_width: Option<f32>,
+    position: ModalPosition,
+    full_screen_below_width: Option<f32>,
     full_span_content: bool,
+    dismiss_policy: DismissPolicy,
+    confirm_close: ConfirmClose,
+    animation: ModalAnimation,
+
+    /// From `0.0` when closed to `1.0` when open.
+    progress: f32,
+
+    /// Whether the modal was closed and plays its close animation.
+    closing: bool,
+
+    /// The order of the layer dimming the background, kept while closing.
+    blocker_order: egui::Order,
+
+    initial_focus: Option<egui::Id>,
+
+    /// The widget focused before the modal opened, focused again when it closes.
+    previous_focus: Option<egui::Id>,
+
+    /// Whether the initial focus is still to be given, which can't be during the sizing pass of
+    /// the window as its widgets are disabled.
+    focus_on_show: bool,
 }
 
 impl Modal {
@@ -114,13 +423,36 @@ impl Modal {
     pub fn new(title: &str) -> Self {
         Self {
             title: title.to_owned(),
+            id: egui::Id::new(title),
             min_width: None,
             min_height: None,
             default_height: None,
+            max_width: None,
+            position: ModalPosition::Top,
+            full_screen_below_width: None,
             full_span_content: false,
+            dismiss_policy: DismissPolicy::ALL,
+            confirm_close: None,
+            animation: ModalAnimation::DEFAULT,
+            progress: 0.0,
+            closing: false,
+            blocker_order: egui::Order::Middle,
+            initial_focus: None,
+            previous_focus: None,
+            focus_on_show: false,
         }
     }
 
+    /// Set the id of the modal, the title by default.
+    ///
+    /// Modals shown at the same time need different ids, so set one when their titles may be the
+    /// same.
+    #[inline]
+    pub fn id(mut self, id: egui::Id) -> Self {
+        self.id = id;
+        self
+    }
+
     /// Set the minimum width of the modal window.
     #[inline]
     pub fn min_width(mut self, min_width: f32) -> Self {
@@ -142,6 +474,51 @@ impl Modal {
         self
     }
 
+    /// Set the maximum width of the modal window.
+    #[inline]
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Set where the modal window is shown, [`ModalPosition::Top`] by default.
+    ///
+    /// ```
+    /// # use egui_ui_refresh::modal::{Modal, ModalHandler, ModalPosition};
+    /// let mut modal_handler = ModalHandler::default();
+    ///
+    /// # egui::__run_test_ui(|ui| {
+    /// let response = ui.button("Filters…");
+    /// if response.clicked() {
+    ///     modal_handler.open();
+    /// }
+    ///
+    /// modal_handler.ui(
+    ///     ui.ctx(),
+    ///     || {
+    ///         Modal::new("Filters")
+    ///             .position(ModalPosition::Anchored(response.rect))
+    ///             .full_screen_below_width(480.0)
+    ///     },
+    ///     |ui, _| {
+    ///         ui.label("Filter content");
+    ///     },
+    /// );
+    /// # });
+    /// ```
+    #[inline]
+    pub fn position(mut self, position: ModalPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Cover the whole window instead when it is narrower than `width`, e.g. on phones.
+    #[inline]
+    pub fn full_screen_below_width(mut self, width: f32) -> Self {
+        self.full_screen_below_width = Some(width);
+        self
+    }
+
     /// Configure the content area of the modal for full span highlighting.
     ///
     /// This includes:
@@ -155,6 +532,53 @@ impl Modal {
         self
     }
 
+    /// Set the ways the user can dismiss the modal, [`DismissPolicy::ALL`] by default.
+    #[inline]
+    pub fn dismiss_policy(mut self, dismiss_policy: DismissPolicy) -> Self {
+        self.dismiss_policy = dismiss_policy;
+        self
+    }
+
+    /// Close the modal when clicking outside of it, `true` by default.
+    #[inline]
+    pub fn close_on_outside_click(mut self, close_on_outside_click: bool) -> Self {
+        self.dismiss_policy.outside_click = close_on_outside_click;
+        self
+    }
+
+    /// Ask before the user dismisses the modal, closing it only if `confirm` returns `true`.
+    ///
+    /// Not called when the content closes the modal itself.
+    #[inline]
+    pub fn confirm_close(mut self, confirm: impl FnMut(DismissReason) -> bool + 'static) -> Self {
+        self.confirm_close = Some(Box::new(confirm));
+        self
+    }
+
+    /// Focus this widget when the modal opens, instead of the first focusable widget of the
+    /// content, which is only known once Tab moved to it.
+    ///
+    /// Use [`egui::TextEdit::id`] or [`egui::Response::id`] to know the id of a widget.
+    #[inline]
+    pub fn initial_focus(mut self, id: egui::Id) -> Self {
+        self.initial_focus = Some(id);
+        self
+    }
+
+    /// Set the open and close animations, [`ModalAnimation::DEFAULT`] by default.
+    #[inline]
+    pub fn animation(mut self, animation: ModalAnimation) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Whether a modal was shown this frame or the previous one, and isn't closing.
+    ///
+    /// Use it to ignore the shortcuts of the ui underneath while a modal is open.
+    pub fn is_any_open(ctx: &egui::Context) -> bool {
+        ModalStack::is_any_shown(ctx)
+    }
+
     /// Show the modal window.
     ///
     /// Typically called by [`ModalHandler::ui`].
@@ -163,26 +587,74 @@ impl Modal {
         ctx: &egui::Context,
         content_ui: impl FnOnce(&mut egui::Ui, &mut bool) -> R,
     ) -> ModalResponse<R> {
-        Self::dim_background(ctx);
+        let id = self.id;
+        let closing = self.closing;
+        let visibility = self.animate(ctx);
 
-        let mut open = ctx.input(|i| !i.key_pressed(egui::Key::Escape));
+        // Once closed, the modal is drawn without taking part in the stack nor blocking input
+        let mut focus_escaped = false;
+        let level = if closing {
+            ModalLevel {
+                below: Vec::new(),
+                is_top: false,
+                opened: false,
+            }
+        } else {
+            let level = ModalStack::show(ctx, id);
+            if level.opened {
+                self.previous_focus = ctx.memory(|mem| mem.focused());
+            }
+            // When opening, the focus is moved to the modal rather than trapped in it
+            focus_escaped = Self::release_focus_underneath(ctx, &level.below) && !level.opened;
+            self.blocker_order = Self::blocker_order(ctx, id, &level);
+            level
+        };
+        if level.opened {
+            self.focus_on_show = true;
+        }
+        let blocker = self.block_input(ctx, id, visibility);
+        if level.opened {
+            ctx.move_to_top(egui::LayerId::new(egui::Order::Foreground, id));
+        }
+        self.scale_window(ctx, id, visibility);
 
-        let screen_height = ctx.screen_rect().height();
-        let modal_vertical_margins = (75.0).at_most(screen_height * 0.1);
+        // Only the topmost modal handles Escape, consuming it for the ones below
+        let policy = self.dismiss_policy;
+        let dismiss_request = if closing {
+            None
+        } else if policy.escape
+            && level.is_top
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+        {
+            Some(DismissReason::Escape)
+        } else if policy.outside_click
+            && blocker.contains_pointer()
+            && ctx.input(|i| i.pointer.any_pressed())
+        {
+            Some(DismissReason::OutsideClick)
+        } else {
+            None
+        };
+        let mut open = !closing
+            && !dismiss_request
+                .is_some_and(|reason| accept_dismiss(&mut self.confirm_close, reason));
 
         let mut window = egui::Window::new(&self.title)
-            .pivot(egui::Align2::CENTER_TOP)
-            .fixed_pos(ctx.screen_rect().center_top() + egui::vec2(0.0, modal_vertical_margins))
+            .id(id)
             .constrain_to(ctx.screen_rect())
-            .max_height(screen_height - 2.0 * modal_vertical_margins)
+            .order(egui::Order::Foreground)
+            .interactable(!closing)
             .collapsible(false)
             .resizable(true)
-            .frame(egui::Frame {
-                // Note: inner margin are kept to zero so the clip rect is set to the same size as the modal itself,
-                // which is needed for the full-span highlighting behavior.
-                fill: ctx.style().visuals.panel_fill,
-                ..Default::default()
-            })
+            .frame(
+                egui::Frame {
+                    // Note: inner margin are kept to zero so the clip rect is set to the same size as the modal itself,
+                    // which is needed for the full-span highlighting behavior.
+                    fill: ctx.style().visuals.panel_fill,
+                    ..Default::default()
+                }
+                .multiply_with_opacity(self.opacity(visibility)),
+            )
             .title_bar(false);
 
         if let Some(min_width) = self.min_width {
@@ -197,7 +669,32 @@ impl Modal {
             window = window.default_height(default_height);
         }
 
+        if let Some(max_width) = self.max_width {
+            window = window.max_width(max_width);
+        }
+
+        let position = self.effective_position(ctx);
+        window = Self::place_window(ctx, window, position);
+
+        let opacity = self.opacity(visibility);
+        let Self {
+            title,
+            full_span_content,
+            confirm_close,
+            initial_focus,
+            focus_on_show,
+            ..
+        } = self;
         let response = window.show(ctx, |ui| {
+            ui.multiply_opacity(opacity);
+            if *focus_on_show && !ui.is_sizing_pass() {
+                FocusTrap::focus_on_open(ui.ctx(), id, *initial_focus);
+                *focus_on_show = false;
+            }
+            if position == ModalPosition::FullScreen {
+                // Non-resizable windows shrink to their content otherwise
+                ui.set_min_size(ui.available_size());
+            }
             let item_spacing_y = ui.spacing().item_spacing.y;
             ui.spacing_mut().item_spacing.y = 0.0;
 
@@ -208,13 +705,16 @@ impl Modal {
             }
                 .show(ui, |ui| {
                     ui.add_space(theme.view_padding);
-                    Self::title_bar(ui, &self.title, &mut open);
+                    let close_clicked = Self::title_bar(ui, title, policy.close_button);
+                    if close_clicked && accept_dismiss(confirm_close, DismissReason::CloseButton) {
+                        open = false;
+                    }
                     ui.add_space(theme.view_padding);
                     full_span_separator(ui);
 
-                    if self.full_span_content {
+                    if *full_span_content {
                         // no further spacing for the content UI
-                        content_ui(ui, &mut open)
+                        FocusTrap::guard_content(ui, id, |ui| content_ui(ui, &mut open))
                     } else {
                         // we must restore vertical spacing and add view padding at the bottom
                         ui.add_space(item_spacing_y);
@@ -228,7 +728,7 @@ impl Modal {
                         }
                             .show(ui, |ui| {
                                 ui.spacing_mut().item_spacing.y = item_spacing_y;
-                                content_ui(ui, &mut open)
+                                FocusTrap::guard_content(ui, id, |ui| content_ui(ui, &mut open))
                             })
                             .inner
                     }
@@ -236,43 +736,215 @@ impl Modal {
                 .inner
         });
 
-        // Any click outside causes the window to close.
-        let cursor_was_over_window = response
-            .as_ref()
-            .and_then(|response| {
-                ctx.input(|i| i.pointer.interact_pos())
-                    .map(|interact_pos| response.response.rect.contains(interact_pos))
-            })
-            .unwrap_or(false);
-        if !cursor_was_over_window && ctx.input(|i| i.pointer.any_pressed()) {
-            open = false;
+        if level.is_top && open && !self.focus_on_show {
+            FocusTrap::keep_focus(ctx, id, focus_escaped);
+        }
+
+        if !open && !closing {
+            ModalStack::close(ctx, id);
+            if let Some(previous_focus) = self.previous_focus.take() {
+                ctx.memory_mut(|mem| mem.request_focus(previous_focus));
+            }
+            self.closing = true;
+            if self.animation.duration <= 0.0 {
+                self.progress = 0.0;
+            }
+        }
+        let visible = !self.closing || self.progress > 0.0;
+        if !visible {
+            ctx.set_transform_layer(
+                egui::LayerId::new(egui::Order::Foreground, id),
+                egui::emath::TSTransform::IDENTITY,
+            );
         }
 
         ModalResponse {
-            inner: response.and_then(|response| response.inner),
+            // The content of a closed modal is only drawn
+            inner: response
+                .and_then(|response| response.inner)
+                .filter(|_| !closing),
             open,
+            visible,
         }
     }
 
-    /// Dim the background to indicate that the window is modal.
-    #[allow(clippy::needless_pass_by_ref_mut)]
-    fn dim_background(ctx: &egui::Context) {
-        let painter = egui::Painter::new(
-            ctx.clone(),
-            egui::LayerId::new(egui::Order::PanelResizeLine, egui::Id::new("DimLayer")),
-            egui::Rect::EVERYTHING,
-        );
+    /// The [`Modal::position`], unless the window is narrow enough to show the modal full-screen.
+    fn effective_position(&self, ctx: &egui::Context) -> ModalPosition {
+        match self.full_screen_below_width {
+            Some(width) if ctx.screen_rect().width() < width => ModalPosition::FullScreen,
+            _ => self.position,
+        }
+    }
+
+    /// Position and size the window.
+    fn place_window<'open>(
+        ctx: &egui::Context,
+        window: egui::Window<'open>,
+        position: ModalPosition,
+    ) -> egui::Window<'open> {
+        let screen_rect = ctx.screen_rect();
+        let screen_height = screen_rect.height();
+        let modal_vertical_margins = (75.0).at_most(screen_height * 0.1);
+
+        match position {
+            ModalPosition::Top => window
+                .pivot(egui::Align2::CENTER_TOP)
+                .fixed_pos(screen_rect.center_top() + egui::vec2(0.0, modal_vertical_margins))
+                .max_height(screen_height - 2.0 * modal_vertical_margins),
+
+            ModalPosition::Center => window
+                .pivot(egui::Align2::CENTER_CENTER)
+                .fixed_pos(screen_rect.center())
+                .max_height(screen_height - 2.0 * modal_vertical_margins),
+
+            ModalPosition::Anchored(anchor) => {
+                let gap = ctx.style().spacing.item_spacing.y;
+                let space_below = screen_rect.bottom() - anchor.bottom() - gap;
+                let space_above = anchor.top() - screen_rect.top() - gap;
+
+                // Open upwards when there is more room above the anchor
+                let (pivot, pos, max_height) = if space_below >= space_above {
+                    let pos = anchor.left_bottom() + egui::vec2(0.0, gap);
+                    (egui::Align2::LEFT_TOP, pos, space_below)
+                } else {
+                    let pos = anchor.left_top() - egui::vec2(0.0, gap);
+                    (egui::Align2::LEFT_BOTTOM, pos, space_above)
+                };
+                window.pivot(pivot).fixed_pos(pos).max_height(max_height)
+            }
+
+            ModalPosition::BottomSheet => window
+                .pivot(egui::Align2::CENTER_BOTTOM)
+                .fixed_pos(screen_rect.center_bottom())
+                .min_width(screen_rect.width())
+                .max_width(screen_rect.width())
+                .max_height(screen_height - modal_vertical_margins)
+                .resizable([false, true]),
+
+            ModalPosition::FullScreen => window.fixed_rect(screen_rect),
+        }
+    }
+
+    /// Advance the open or close animation, returning how visible the modal is, from `0.0` to
+    /// `1.0`.
+    fn animate(&mut self, ctx: &egui::Context) -> f32 {
+        let duration = self.animation.duration;
+        self.progress = if duration > 0.0 {
+            let step = ctx.input(|i| i.stable_dt) / duration;
+            let progress = if self.closing {
+                self.progress - step
+            } else {
+                self.progress + step
+            };
+            progress.clamp(0.0, 1.0)
+        } else if self.closing {
+            0.0
+        } else {
+            1.0
+        };
+
+        if 0.0 < self.progress && self.progress < 1.0 {
+            ctx.request_repaint();
+        }
+        egui::emath::easing::cubic_out(self.progress)
+    }
+
+    fn opacity(&self, visibility: f32) -> f32 {
+        if self.animation.fade {
+            visibility
+        } else {
+            1.0
+        }
+    }
+
+    /// Zoom the modal window around its center.
+    fn scale_window(&self, ctx: &egui::Context, id: egui::Id, visibility: f32) {
+        let scale = egui::lerp(self.animation.scale..=1.0, visibility);
+        let transform = match ctx.memory(|mem| mem.area_rect(id)) {
+            Some(rect) if scale != 1.0 => {
+                let center = rect.center().to_vec2();
+                egui::emath::TSTransform::from_translation(center)
+                    * egui::emath::TSTransform::from_scaling(scale)
+                    * egui::emath::TSTransform::from_translation(-center)
+            }
+            _ => egui::emath::TSTransform::IDENTITY,
+        };
+        ctx.set_transform_layer(egui::LayerId::new(egui::Order::Foreground, id), transform);
+    }
+
+    /// The order of the layer covering everything underneath the modal.
+    ///
+    /// For the first modal of the stack, the layer sits on top of the [`egui::Order::Middle`]
+    /// windows and panels, and below the [`egui::Order::Foreground`] modal window and the popups
+    /// it opens. The layers of the modals opened on top of it are put above it when they open.
+    fn blocker_order(ctx: &egui::Context, id: egui::Id, level: &ModalLevel) -> egui::Order {
+        let blocker_id = id.with("blocker");
+        if level.below.is_empty() {
+            ctx.move_to_top(egui::LayerId::new(egui::Order::Middle, blocker_id));
+            egui::Order::Middle
+        } else {
+            if level.opened {
+                ctx.move_to_top(egui::LayerId::new(egui::Order::Foreground, blocker_id));
+            }
+            egui::Order::Foreground
+        }
+    }
+
+    /// Cover everything underneath the modal with a layer capturing the pointer, unless the
+    /// modal is closing.
+    fn block_input(&self, ctx: &egui::Context, id: egui::Id, visibility: f32) -> egui::Response {
+        let sense = if self.closing {
+            egui::Sense::hover()
+        } else {
+            egui::Sense::click_and_drag()
+        };
+
+        egui::Area::new(id.with("blocker"))
+            .order(self.blocker_order)
+            .interactable(!self.closing)
+            .fixed_pos(egui::Pos2::ZERO)
+            .show(ctx, |ui| {
+                let screen_rect = ctx.screen_rect();
+                Self::dim_background(ui.painter(), screen_rect, self.opacity(visibility));
+                ui.allocate_rect(screen_rect, sense)
+            })
+            .inner
+    }
+
+    /// Take the keyboard focus away from the widgets underneath the modal, including the modals
+    /// below it in the stack, returning whether one had it.
+    fn release_focus_underneath(ctx: &egui::Context, modals_below: &[egui::Id]) -> bool {
+        let Some(focused) = ctx.memory(|mem| mem.focused()) else {
+            return false;
+        };
+        let is_underneath = ctx.read_response(focused).is_some_and(|response| {
+            response.layer_id.order < egui::Order::Foreground
+                || modals_below.contains(&response.layer_id.id)
+        });
+        if is_underneath {
+            ctx.memory_mut(|mem| mem.surrender_focus(focused));
+        }
+        is_underneath
+    }
+
+    /// Dim the background to indicate that the window is modal, `opacity` fading it in and out.
+    fn dim_background(painter: &egui::Painter, rect: egui::Rect, opacity: f32) {
         painter.add(egui::Shape::rect_filled(
-            ctx.screen_rect(),
+            rect,
             egui::Rounding::ZERO,
-            egui::Color32::from_black_alpha(128),
+            egui::Color32::from_black_alpha(128).gamma_multiply(opacity),
         ));
     }
 
-    /// Display a title bar in our own style.
-    fn title_bar(ui: &mut Ui, title: &str, open: &mut bool) {
+    /// Display a title bar in our own style, returning whether the close button was clicked.
+    fn title_bar(ui: &mut Ui, title: &str, close_button: bool) -> bool {
         ui.horizontal(|ui| {
-            ui.strong(title);
+            let family = font_family_semibold();
+            if ui.fonts(|fonts| fonts.families().contains(&family)) {
+                ui.label(egui::RichText::new(title).strong().family(family));
+            } else {
+                ui.strong(title);
+            }
 
             ui.add_space(16.0);
 
@@ -281,14 +953,332 @@ impl Modal {
                 egui::Layout::right_to_left(egui::Align::Center),
                 None,
             );
-            // .tint(self.ui().visuals().widgets.inactive.fg_stroke.color);
-            if ui.button(icon(icons::CLOSE, 16.0)).clicked() {
-                *open = false;
+            close_button && ui.add(icons::CLOSE.button()).clicked()
+        })
+        .inner
+    }
+}
+
+/// The modals currently shown, from the bottom to the top, with the last frame they were shown.
+///
+/// Modals are pushed when first shown, and removed when closed or not shown for a frame.
+#[derive(Clone, Default)]
+struct ModalStack(Vec<(egui::Id, u64)>);
+
+/// The position of a modal in the [`ModalStack`].
+struct ModalLevel {
+    /// The modals below, from the bottom.
+    below: Vec<egui::Id>,
+
+    /// Whether no modal is above.
+    is_top: bool,
+
+    /// Whether the modal is shown for the first time.
+    opened: bool,
+}
+
+impl ModalStack {
+    fn id() -> egui::Id {
+        egui::Id::new("modal_stack")
+    }
+
+    /// Register the modal `id` as shown this frame.
+    fn show(ctx: &egui::Context, id: egui::Id) -> ModalLevel {
+        let frame_nr = ctx.frame_nr();
+        ctx.data_mut(|data| {
+            let Self(stack) = data.get_temp_mut_or_default::<Self>(Self::id());
+            stack.retain(|(_, shown)| *shown + 1 >= frame_nr);
+
+            let position = stack.iter().position(|(modal, _)| *modal == id);
+            let opened = position.is_none();
+            let position = position.unwrap_or_else(|| {
+                stack.push((id, frame_nr));
+                stack.len() - 1
+            });
+            stack[position].1 = frame_nr;
+
+            ModalLevel {
+                below: stack[..position].iter().map(|(modal, _)| *modal).collect(),
+                is_top: position + 1 == stack.len(),
+                opened,
             }
+        })
+    }
+
+    /// Whether the modal `id` is shown, with no modal above it.
+    fn is_top(ctx: &egui::Context, id: egui::Id) -> bool {
+        ctx.data(|data| {
+            data.get_temp::<Self>(Self::id())
+                .and_then(|Self(stack)| stack.last().map(|(top, _)| *top == id))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Whether a modal was shown this frame or the previous one.
+    fn is_any_shown(ctx: &egui::Context) -> bool {
+        let frame_nr = ctx.frame_nr();
+        ctx.data(|data| {
+            data.get_temp::<Self>(Self::id()).is_some_and(|Self(stack)| {
+                stack.iter().any(|(_, shown)| *shown + 1 >= frame_nr)
+            })
+        })
+    }
+
+    fn close(ctx: &egui::Context, id: egui::Id) {
+        ctx.data_mut(|data| {
+            let Self(stack) = data.get_temp_mut_or_default::<Self>(Self::id());
+            stack.retain(|(modal, _)| *modal != id);
         });
     }
 }
 
+/// The action chosen in a [`ModalFooter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FooterAction {
+    Primary,
+    Secondary,
+}
+
+/// The footer of a [`Modal`]: a full-span separator above right-aligned action buttons.
+///
+/// The primary button is the rightmost one, filled with [`RefreshedTheme::selection`], and is
+/// also triggered by Enter, unless another modal is on top or the focused widget reacts to Enter
+/// itself: a multiline text input, or a button or checkbox, which Enter clicks. A single-line text
+/// input gives up the focus on Enter, which then triggers the primary button. Show the footer at
+/// the end of the modal content:
+///
+/// ```
+/// # use egui_ui_refresh::modal::{FooterAction, Modal, ModalFooter, ModalHandler};
+/// let mut modal_handler = ModalHandler::default();
+///
+/// # egui::__run_test_ui(|ui| {
+/// modal_handler.ui(ui.ctx(), || Modal::new("Save changes?"), |ui, open| {
+///     ui.label("Your changes will be lost otherwise.");
+///
+///     match ModalFooter::new("Save").secondary("Don't save").show(ui) {
+///         Some(FooterAction::Primary) => *open = false, // save
+///         Some(FooterAction::Secondary) => *open = false,
+///         None => {}
+///     }
+/// });
+/// # });
+/// ```
+#[must_use = "You should show the footer with `footer.show(ui)`"]
+pub struct ModalFooter {
+    primary: String,
+    secondary: Option<String>,
+    primary_enabled: bool,
+    primary_color: Option<egui::Color32>,
+}
+
+impl ModalFooter {
+    /// A footer with only a primary button.
+    pub fn new(primary: impl Into<String>) -> Self {
+        Self {
+            primary: primary.into(),
+            secondary: None,
+            primary_enabled: true,
+            primary_color: None,
+        }
+    }
+
+    /// Add a secondary button, left of the primary one.
+    #[inline]
+    pub fn secondary(mut self, secondary: impl Into<String>) -> Self {
+        self.secondary = Some(secondary.into());
+        self
+    }
+
+    /// Disable the primary button and Enter, e.g. while a form is invalid.
+    #[inline]
+    pub fn primary_enabled(mut self, primary_enabled: bool) -> Self {
+        self.primary_enabled = primary_enabled;
+        self
+    }
+
+    /// Fill the primary button with this color instead of [`RefreshedTheme::selection`], e.g.
+    /// red for destructive actions.
+    #[inline]
+    pub fn primary_color(mut self, primary_color: egui::Color32) -> Self {
+        self.primary_color = Some(primary_color);
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Option<FooterAction> {
+        let Self {
+            primary,
+            secondary,
+            primary_enabled,
+            primary_color,
+        } = self;
+        let theme = RefreshedTheme::get();
+
+        let item_spacing_y = ui.spacing().item_spacing.y;
+        ui.add_space(theme.view_padding - item_spacing_y);
+        full_span_separator(ui);
+        ui.add_space(theme.view_padding);
+
+        let mut action = ui
+            .horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let primary_button =
+                        egui::Button::new(egui::RichText::new(primary).color(egui::Color32::WHITE))
+                            .fill(primary_color.unwrap_or(theme.selection));
+                    if ui.add_enabled(primary_enabled, primary_button).clicked() {
+                        return Some(FooterAction::Primary);
+                    }
+                    if secondary.is_some_and(|secondary| ui.button(secondary).clicked()) {
+                        return Some(FooterAction::Secondary);
+                    }
+                    None
+                })
+                .inner
+            })
+            .inner;
+
+        let ctx = ui.ctx().clone();
+        if action.is_none()
+            && primary_enabled
+            && !FocusTrap::focus_takes_enter(&ctx)
+            && ModalStack::is_top(&ctx, ui.layer_id().id)
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter))
+        {
+            action = Some(FooterAction::Primary);
+        }
+        action
+    }
+}
+
+/// Keeps the keyboard focus within the content of a [`Modal`].
+///
+/// egui doesn't tell which widgets can take the focus, so the content is surrounded by two
+/// invisible focusable guards: tabbing past the last widget of the content focuses the end guard,
+/// and Shift+Tab on the first one focuses the start guard. This is how the first and last widgets
+/// are found, and the focus is sent back to them. Until then, the start guard holds the focus
+/// in their place, the next Tab moving to the first widget.
+#[derive(Clone, Copy, Default)]
+struct FocusTrap {
+    /// The first focusable widget of the content, once known.
+    first: Option<egui::Id>,
+
+    /// The last focusable widget of the content, once known.
+    last: Option<egui::Id>,
+
+    /// The widget of the modal focused at the end of the last frame.
+    focused: Option<egui::Id>,
+
+    /// Whether the last Tab press was with Shift.
+    backwards: bool,
+}
+
+impl FocusTrap {
+    fn id(modal: egui::Id) -> egui::Id {
+        modal.with("focus_trap")
+    }
+
+    /// The start and end guards of the modal `modal`.
+    fn guards(modal: egui::Id) -> [egui::Id; 2] {
+        [modal.with("focus_start"), modal.with("focus_end")]
+    }
+
+    fn load(ctx: &egui::Context, modal: egui::Id) -> Self {
+        ctx.data(|data| data.get_temp(Self::id(modal)))
+            .unwrap_or_default()
+    }
+
+    fn store(self, ctx: &egui::Context, modal: egui::Id) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(modal), self));
+    }
+
+    /// Whether the focused widget reacts to Enter itself: a multiline text input inserts a new
+    /// line, and a button or checkbox is clicked.
+    ///
+    /// A single-line text input gives up the focus on Enter before the footer is shown, and the
+    /// focus guards can't be clicked.
+    fn focus_takes_enter(ctx: &egui::Context) -> bool {
+        ctx.memory(|mem| mem.focused())
+            .and_then(|focused| ctx.read_response(focused))
+            .is_some_and(|response| response.sense.click)
+    }
+
+    /// Focus `initial_focus`, else the first widget of the content if known from the last time
+    /// the modal was open, else the start guard.
+    fn focus_on_open(ctx: &egui::Context, modal: egui::Id, initial_focus: Option<egui::Id>) {
+        let [start, _] = Self::guards(modal);
+        let mut trap = Self::load(ctx, modal);
+        // egui can't move the focus to the next widget, the start guard holds it until Tab does
+        let target = initial_focus.or(trap.first).unwrap_or(start);
+        ctx.memory_mut(|mem| mem.request_focus(target));
+        trap.focused = Some(target);
+        trap.store(ctx, modal);
+    }
+
+    /// Show `content` between the focus guards.
+    fn guard_content<R>(ui: &mut Ui, modal: egui::Id, content: impl FnOnce(&mut Ui) -> R) -> R {
+        let [start, end] = Self::guards(modal);
+        let guard = |ui: &mut Ui, id| {
+            let rect = egui::Rect::from_min_size(ui.cursor().min, egui::Vec2::ZERO);
+            ui.interact(rect, id, egui::Sense::focusable_noninteractive());
+        };
+
+        guard(ui, start);
+        let inner = content(ui);
+        guard(ui, end);
+        inner
+    }
+
+    /// Send the focus back into the content when it leaves it, `escaped` telling whether it went
+    /// to a widget underneath the modal.
+    fn keep_focus(ctx: &egui::Context, modal: egui::Id, escaped: bool) {
+        let [start, end] = Self::guards(modal);
+        let mut trap = Self::load(ctx, modal);
+
+        let tab = ctx.input(|i| i.key_pressed(egui::Key::Tab).then_some(i.modifiers.shift));
+        if let Some(backwards) = tab {
+            trap.backwards = backwards;
+        }
+        let previous = trap.focused.filter(|previous| *previous != start && *previous != end);
+        let focused = ctx.memory(|mem| mem.focused());
+
+        let redirect = if focused == Some(end) {
+            // Tabbed past the last widget
+            trap.last = previous.or(trap.last);
+            Some(trap.first.unwrap_or(start))
+        } else if focused == Some(start) && previous.is_some() {
+            // Shift+Tab on the first widget, the focus moving to the start guard a frame later
+            trap.first = previous;
+            trap.last
+        } else if escaped {
+            let target = if trap.backwards { trap.last } else { trap.first };
+            Some(target.unwrap_or(start))
+        } else {
+            if tab == Some(false) && trap.focused == Some(start) {
+                // Tabbed from the start guard
+                trap.first = focused.filter(|focused| *focused != start).or(trap.first);
+            }
+            None
+        };
+
+        if let Some(target) = redirect {
+            ctx.memory_mut(|mem| mem.request_focus(target));
+            trap.focused = Some(target);
+        } else if focused.map_or(true, |focused| {
+            ctx.read_response(focused)
+                .is_some_and(|response| response.layer_id.id == modal)
+        }) {
+            trap.focused = focused;
+        }
+        trap.store(ctx, modal);
+    }
+}
+
+type ConfirmClose = Option<Box<dyn FnMut(DismissReason) -> bool>>;
+
+/// Whether the [`Modal::confirm_close`] hook lets the user dismiss the modal.
+fn accept_dismiss(confirm_close: &mut ConfirmClose, reason: DismissReason) -> bool {
+    confirm_close.as_mut().map_or(true, |confirm| confirm(reason))
+}
+
 fn full_span_separator(ui: &mut Ui) -> egui::Response {
     let height = 1.0;
 
@@ -0,0 +1,316 @@
+use std::ops::RangeInclusive;
+
+use egui::style::NumberFormatter;
+use egui::NumExt;
+
+/// The minus character: <https://www.compart.com/en/unicode/U+2212>
+///
+/// Looks slightly different from the normal hyphen `-`.
+const MINUS: char = '−';
+
+/// Thin space, the default thousands separator: <https://en.wikipedia.org/wiki/Thin_space>
+const THIN_SPACE: char = '\u{2009}';
+
+/// SI suffixes for the powers of 1000, starting at 1000¹.
+const SI_SUFFIXES: [char; 6] = ['k', 'M', 'G', 'T', 'P', 'E'];
+
+/// How numbers are written, e.g. in [`egui::DragValue`] and [`egui::Slider`].
+///
+/// [`crate::RefreshedTheme::apply`] installs the [`crate::RefreshedTheme::number_format`] of the
+/// theme as the egui [`NumberFormatter`].
+///
+/// ```
+/// # use egui_ui_refresh::number_format::NumberFormat;
+/// assert_eq!(NumberFormat::DEFAULT.format(-1234.5, 1), "−1\u{2009}234.5");
+///
+/// let french = NumberFormat::DEFAULT.with_separators(Some('\u{202F}'), ',');
+/// assert_eq!(french.format(1234.5, 2), "1\u{202F}234,50");
+/// assert_eq!(french.parse("1\u{202F}234,50"), Some(1234.5));
+///
+/// let si = NumberFormat::DEFAULT.with_si_suffixes(true);
+/// assert_eq!(si.format(12_345.0, 1), "12.3k");
+/// assert_eq!(si.parse("12.3k"), Some(12_300.0));
+///
+/// let scientific = NumberFormat::DEFAULT.with_scientific_thresholds(1e-3, 1e6);
+/// assert_eq!(scientific.format(0.000_012, 1), "1.2e-5");
+/// assert_eq!(scientific.format(3e9, 0), "3e9");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberFormat {
+    /// Separator between groups of three digits, `None` to disable grouping.
+    pub thousands_separator: Option<char>,
+
+    /// Separator between the integer and the fractional part.
+    pub decimal_separator: char,
+
+    /// Write negative numbers with the unicode minus sign `−` instead of the hyphen `-`.
+    pub unicode_minus: bool,
+
+    /// Use scientific notation for non-zero values whose magnitude is below this.
+    pub scientific_below: f64,
+
+    /// Use scientific notation for values whose magnitude is at least this.
+    pub scientific_above: f64,
+
+    /// Write values of magnitude 1000 and above with SI suffixes (`12.3k`, `4.5M`), instead of
+    /// scientific notation. Values past the largest suffix `E` are still in scientific notation.
+    pub si_suffixes: bool,
+}
+
+impl NumberFormat {
+    pub const DEFAULT: Self = Self {
+        thousands_separator: Some(THIN_SPACE),
+        decimal_separator: '.',
+        unicode_minus: true,
+        scientific_below: 0.0,
+        scientific_above: 1e15,
+        si_suffixes: false,
+    };
+
+    /// Separators of the integer and fractional parts, e.g. `(Some(','), '.')` in English or
+    /// `(Some('.'), ',')` in German.
+    #[inline]
+    pub const fn with_separators(mut self, thousands: Option<char>, decimal: char) -> Self {
+        self.thousands_separator = thousands;
+        self.decimal_separator = decimal;
+        self
+    }
+
+    #[inline]
+    pub const fn with_unicode_minus(mut self, unicode_minus: bool) -> Self {
+        self.unicode_minus = unicode_minus;
+        self
+    }
+
+    /// Use scientific notation for non-zero magnitudes below `below` or at least `above`.
+    #[inline]
+    pub const fn with_scientific_thresholds(mut self, below: f64, above: f64) -> Self {
+        self.scientific_below = below;
+        self.scientific_above = above;
+        self
+    }
+
+    #[inline]
+    pub const fn with_si_suffixes(mut self, si_suffixes: bool) -> Self {
+        self.si_suffixes = si_suffixes;
+        self
+    }
+
+    /// An egui [`NumberFormatter`] using this format.
+    pub fn number_formatter(self) -> NumberFormatter {
+        NumberFormatter::new(move |value, decimal_range| {
+            self.format_with_decimals_in_range(value, decimal_range)
+        })
+    }
+
+    /// A parser for [`egui::DragValue::custom_parser`], needed when the separators are not the
+    /// egui defaults or with SI suffixes.
+    pub fn parser(self) -> impl 'static + Fn(&str) -> Option<f64> {
+        move |text| self.parse(text)
+    }
+
+    /// Format `value` with as few decimals in `decimal_range` as possible while still
+    /// round-tripping it.
+    pub fn format_with_decimals_in_range(
+        &self,
+        value: f64,
+        decimal_range: RangeInclusive<usize>,
+    ) -> String {
+        let epsilon = 16.0 * f32::EPSILON; // margin large enough to handle most peoples round-tripping needs
+
+        let min_decimals = *decimal_range.start();
+        let max_decimals = *decimal_range.end();
+        debug_assert!(min_decimals <= max_decimals);
+        debug_assert!(max_decimals < 100);
+        let max_decimals = max_decimals.at_most(16);
+        let min_decimals = min_decimals.at_most(max_decimals);
+
+        if min_decimals < max_decimals {
+            // Try using a few decimals as possible, and then add more until we have enough precision
+            // to round-trip the number.
+            for decimals in min_decimals..max_decimals {
+                let text = self.format(value, decimals);
+                if let Some(parsed) = self.parse(&text) {
+                    if egui::emath::almost_equal(parsed as f32, value as f32, epsilon) {
+                        // Enough precision to show the value accurately - good!
+                        return text;
+                    }
+                }
+            }
+            // The value has more precision than we expected.
+            // Probably the value was set not by the slider, but from outside.
+            // In any case: show the full value
+        }
+
+        // Use max decimals
+        self.format(value, max_decimals)
+    }
+
+    /// Format `value` with exactly `decimals` decimals.
+    ///
+    /// The returned value is for human eyes only, use [`Self::parse`] to read it back.
+    pub fn format(&self, value: f64, decimals: usize) -> String {
+        if value.is_nan() {
+            return "NaN".to_owned();
+        }
+
+        let sign = if value.is_sign_negative() && value != 0.0 {
+            if self.unicode_minus {
+                MINUS.to_string()
+            } else {
+                "-".to_owned()
+            }
+        } else {
+            String::new()
+        };
+        let value = value.abs();
+
+        let abs_string = if value == f64::INFINITY {
+            "∞".to_owned()
+        } else if self.si_suffixes && round(value, decimals) >= 1000.0 {
+            self.format_si(value, decimals)
+        } else if value >= self.scientific_above || (value != 0.0 && value < self.scientific_below)
+        {
+            self.format_scientific(value, decimals)
+        } else {
+            self.format_positional(value, decimals)
+        };
+
+        format!("{sign}{abs_string}")
+    }
+
+    /// Format a positive finite value with the SI suffix of its power of 1000, or in scientific
+    /// notation past the largest suffix.
+    fn format_si(&self, value: f64, decimals: usize) -> String {
+        let mut power = ((value.log10() / 3.0).floor() as i32).max(1);
+        // Rounding may carry the value over to the next power, e.g. 999 950 to 1 000.0k
+        if round(value / 1000_f64.powi(power), decimals) >= 1000.0 {
+            power += 1;
+        }
+
+        match SI_SUFFIXES.get(power as usize - 1) {
+            Some(suffix) => format!(
+                "{}{suffix}",
+                self.format_positional(value / 1000_f64.powi(power), decimals)
+            ),
+            None => self.format_scientific(value, decimals),
+        }
+    }
+
+    /// Format a positive finite value in scientific notation.
+    fn format_scientific(&self, value: f64, decimals: usize) -> String {
+        format!("{value:.decimals$e}").replace('.', &self.decimal_separator.to_string())
+    }
+
+    /// Format a positive finite value without exponent.
+    fn format_positional(&self, value: f64, decimals: usize) -> String {
+        let formatted = format_plain(value, decimals);
+        let (integer_part, fractional_part) = match formatted.split_once('.') {
+            Some((integer_part, fractional_part)) => (integer_part, Some(fractional_part)),
+            None => (formatted.as_str(), None),
+        };
+
+        let mut result = String::with_capacity(formatted.len() + formatted.len() / 3);
+        for (i, c) in integer_part.chars().enumerate() {
+            if let Some(separator) = self.thousands_separator {
+                if i > 0 && (integer_part.len() - i) % 3 == 0 {
+                    result.push(separator);
+                }
+            }
+            result.push(c);
+        }
+
+        if let Some(fractional_part) = fractional_part {
+            result.push(self.decimal_separator);
+            // Only group long fractional parts, and only with spaces which can't be mistaken for
+            // a decimal separator.
+            let separator = self
+                .thousands_separator
+                .filter(|separator| separator.is_whitespace())
+                .filter(|_| fractional_part.len() >= MIN_DECIMALS_FOR_SEPARATORS);
+            for (i, c) in fractional_part.chars().enumerate() {
+                if let Some(separator) = separator {
+                    if i > 0 && i % 3 == 0 {
+                        result.push(separator);
+                    }
+                }
+                result.push(c);
+            }
+        }
+
+        result
+    }
+
+    /// Parse a number written with this format.
+    ///
+    /// Whitespace (e.g. thousands separators) is ignored, and the unicode minus `−` is accepted.
+    /// Numbers are always read with the separators of this format, so with `.` as the thousands
+    /// separator, `1.5` is 15.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let mut text: String = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .filter(|c| Some(*c) != self.thousands_separator || *c == self.decimal_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect();
+
+        let mut multiplier = 1.0;
+        if self.si_suffixes {
+            if let Some(power) = text
+                .chars()
+                .last()
+                .and_then(|last| SI_SUFFIXES.iter().position(|suffix| *suffix == last))
+            {
+                text.pop();
+                multiplier = 1000_f64.powi(power as i32 + 1);
+            }
+        }
+
+        parse_plain(&text).map(|value| value * multiplier)
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Fractional parts are only grouped when they have at least this many digits.
+const MIN_DECIMALS_FOR_SEPARATORS: usize = 6;
+
+/// Significant digits of an `f64` shown before switching to scientific notation.
+const F64_PRECISION: usize = 15;
+
+/// Format a positive finite value with `decimals` decimals, a `.` and no separators.
+///
+/// Values with more than [`F64_PRECISION`] integer digits are written in scientific notation,
+/// the decimals being capped so that no more than [`F64_PRECISION`] digits are shown.
+fn format_plain(value: f64, decimals: usize) -> String {
+    let magnitude = value.log10();
+    let max_decimals = F64_PRECISION as f64 - magnitude.max(0.0);
+
+    if max_decimals < 0.0 {
+        // More digits than we have precision
+        format!("{:.*e}", F64_PRECISION - 1, value)
+    } else {
+        let decimals = decimals.min(max_decimals as usize);
+        format!("{value:.decimals$}")
+    }
+}
+
+/// `value` rounded to `decimals` decimals, like it is written by [`format_plain`].
+fn round(value: f64, decimals: usize) -> f64 {
+    format_plain(value, decimals).parse().unwrap_or(value)
+}
+
+/// Parse a number with a `.` decimal separator, ignoring whitespace and accepting the unicode
+/// minus.
+fn parse_plain(text: &str) -> Option<f64> {
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == MINUS { '-' } else { c })
+        .collect();
+    text.parse().ok()
+}
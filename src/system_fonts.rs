@@ -0,0 +1,303 @@
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use egui::{FontData, FontDefinitions, FontFamily};
+
+use crate::fonts::{text_families, FontPosition, FontsBuilder};
+
+/// Writing systems that can be covered by a system font fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Bengali,
+    Tamil,
+    Thai,
+    Georgian,
+    Hangul,
+    Japanese,
+    Han,
+}
+
+impl Script {
+    pub const ALL: [Self; 13] = [
+        Self::Greek,
+        Self::Cyrillic,
+        Self::Armenian,
+        Self::Hebrew,
+        Self::Arabic,
+        Self::Devanagari,
+        Self::Bengali,
+        Self::Tamil,
+        Self::Thai,
+        Self::Georgian,
+        Self::Hangul,
+        Self::Japanese,
+        Self::Han,
+    ];
+
+    /// Unicode blocks of the script.
+    pub fn ranges(self) -> &'static [RangeInclusive<u32>] {
+        match self {
+            Self::Greek => &[0x0370..=0x03FF, 0x1F00..=0x1FFF],
+            Self::Cyrillic => &[0x0400..=0x052F],
+            Self::Armenian => &[0x0530..=0x058F],
+            Self::Hebrew => &[0x0590..=0x05FF],
+            Self::Arabic => &[0x0600..=0x06FF, 0x0750..=0x077F],
+            Self::Devanagari => &[0x0900..=0x097F],
+            Self::Bengali => &[0x0980..=0x09FF],
+            Self::Tamil => &[0x0B80..=0x0BFF],
+            Self::Thai => &[0x0E00..=0x0E7F],
+            Self::Georgian => &[0x10A0..=0x10FF],
+            Self::Hangul => &[0x1100..=0x11FF, 0x3130..=0x318F, 0xAC00..=0xD7AF],
+            Self::Japanese => &[0x3040..=0x309F, 0x30A0..=0x30FF],
+            Self::Han => &[0x3000..=0x303F, 0x4E00..=0x9FFF],
+        }
+    }
+
+    /// A few common characters a font must have to be considered for this script.
+    pub fn sample(self) -> &'static str {
+        match self {
+            Self::Greek => "αβγΩ",
+            Self::Cyrillic => "абвЖ",
+            Self::Armenian => "աբգ",
+            Self::Hebrew => "אבג",
+            Self::Arabic => "ابتع",
+            Self::Devanagari => "कखग",
+            Self::Bengali => "কখগ",
+            Self::Tamil => "கஙச",
+            Self::Thai => "กขค",
+            Self::Georgian => "აბგ",
+            Self::Hangul => "한글",
+            Self::Japanese => "あいアイ",
+            Self::Han => "中文字",
+        }
+    }
+}
+
+/// A system font chosen as a fallback.
+#[derive(Debug, Clone)]
+pub struct SystemFont {
+    /// Path of the font file.
+    pub path: PathBuf,
+
+    /// Index of the font in a font collection (`.ttc`), 0 otherwise.
+    pub index: u32,
+
+    /// Scripts this font has been chosen for.
+    pub scripts: Vec<Script>,
+}
+
+impl SystemFont {
+    /// Name of the font in [`FontDefinitions::font_data`].
+    pub fn name(&self) -> String {
+        format!("system:{}#{}", self.path.display(), self.index)
+    }
+}
+
+/// Discovers installed fonts to use as fallbacks for the scripts the bundled fonts don't cover.
+///
+/// Fonts are searched in the standard Linux font directories (`/usr/share/fonts`,
+/// `/usr/local/share/fonts`, `$XDG_DATA_HOME/fonts` and `~/.fonts`) and in the directories added
+/// with [`SystemFallbacks::with_dir`]. For each script, the regular font covering the most of it
+/// is picked.
+///
+/// ```no_run
+/// # use egui_ui_refresh::system_fonts::SystemFallbacks;
+/// # egui::__run_test_ctx(|ctx| {
+/// let fonts = SystemFallbacks::new().with_dir("/opt/my-app/fonts").fonts();
+/// ctx.set_fonts(fonts);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct SystemFallbacks {
+    dirs: Vec<PathBuf>,
+    scripts: Vec<Script>,
+}
+
+impl Default for SystemFallbacks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemFallbacks {
+    /// Search the standard font directories for all the known [`Script`]s.
+    pub fn new() -> Self {
+        let mut dirs = vec![
+            PathBuf::from("/usr/share/fonts"),
+            PathBuf::from("/usr/local/share/fonts"),
+        ];
+        if let Some(data_home) = std::env::var_os("XDG_DATA_HOME") {
+            dirs.push(Path::new(&data_home).join("fonts"));
+        } else if let Some(home) = std::env::var_os("HOME") {
+            dirs.push(Path::new(&home).join(".local/share/fonts"));
+        }
+        if let Some(home) = std::env::var_os("HOME") {
+            dirs.push(Path::new(&home).join(".fonts"));
+        }
+
+        Self {
+            dirs,
+            scripts: Script::ALL.to_vec(),
+        }
+    }
+
+    /// Also search this directory, with priority over the standard ones.
+    #[inline]
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dirs.insert(0, dir.into());
+        self
+    }
+
+    /// Only look for fallbacks for these scripts.
+    #[inline]
+    pub fn scripts(mut self, scripts: &[Script]) -> Self {
+        self.scripts = scripts.to_vec();
+        self
+    }
+
+    /// The bundled fonts from [`crate::fonts::fonts`] followed by the system fallbacks.
+    pub fn fonts(&self) -> FontDefinitions {
+        self.apply(FontsBuilder::new()).build()
+    }
+
+    /// Append the system fallbacks to the text families of `builder`.
+    ///
+    /// Scripts already covered by the fonts of the proportional family are skipped.
+    pub fn apply(&self, mut builder: FontsBuilder) -> FontsBuilder {
+        let missing = self
+            .scripts
+            .iter()
+            .copied()
+            .filter(|script| {
+                !family_covers(builder.definitions(), &FontFamily::Proportional, *script)
+            })
+            .collect::<Vec<_>>();
+
+        for font in self.discover(&missing) {
+            let Ok(data) = std::fs::read(&font.path) else {
+                continue;
+            };
+            let mut data = FontData::from_owned(data);
+            data.index = font.index;
+            builder = builder.add_font(font.name(), data, &text_families(), FontPosition::Last);
+        }
+        builder
+    }
+
+    /// Find the best font for each of `scripts`.
+    ///
+    /// A font covering multiple scripts is only returned once.
+    pub fn discover(&self, scripts: &[Script]) -> Vec<SystemFont> {
+        if scripts.is_empty() {
+            return Vec::new();
+        }
+
+        let mut files = Vec::new();
+        for dir in &self.dirs {
+            collect_font_files(dir, 0, &mut files);
+        }
+
+        // Best candidate for each script: (coverage, path, index)
+        let mut best: Vec<Option<(usize, PathBuf, u32)>> = vec![None; scripts.len()];
+        for path in files {
+            let Ok(data) = std::fs::read(&path) else {
+                continue;
+            };
+            let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+            for index in 0..count {
+                let Ok(face) = ttf_parser::Face::parse(&data, index) else {
+                    continue;
+                };
+                if face.is_bold() || face.is_italic() || face.is_oblique() {
+                    continue;
+                }
+                for (script, best) in scripts.iter().zip(best.iter_mut()) {
+                    if !script.sample().chars().all(|c| face.glyph_index(c).is_some()) {
+                        continue;
+                    }
+                    let coverage = coverage(&face, *script);
+                    if best.as_ref().map_or(true, |(best, _, _)| coverage > *best) {
+                        *best = Some((coverage, path.clone(), index));
+                    }
+                }
+            }
+        }
+
+        let mut fonts: Vec<SystemFont> = Vec::new();
+        for (script, best) in scripts.iter().zip(best) {
+            let Some((_, path, index)) = best else {
+                continue;
+            };
+            if let Some(font) = fonts
+                .iter_mut()
+                .find(|font| font.path == path && font.index == index)
+            {
+                font.scripts.push(*script);
+            } else {
+                fonts.push(SystemFont {
+                    path,
+                    index,
+                    scripts: vec![*script],
+                });
+            }
+        }
+        fonts
+    }
+}
+
+const MAX_DIR_DEPTH: usize = 8;
+
+fn collect_font_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    if depth > MAX_DIR_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect::<Vec<_>>();
+    // Deterministic choice between fonts with the same coverage
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_font_files(&path, depth + 1, files);
+        } else if path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        }) {
+            files.push(path);
+        }
+    }
+}
+
+/// Number of codepoints of the script the font has a glyph for.
+fn coverage(face: &ttf_parser::Face<'_>, script: Script) -> usize {
+    script
+        .ranges()
+        .iter()
+        .flat_map(|range| range.clone())
+        .filter_map(char::from_u32)
+        .filter(|c| face.glyph_index(*c).is_some())
+        .count()
+}
+
+/// Whether one of the fonts of `family` has all the sample characters of `script`.
+fn family_covers(definitions: &FontDefinitions, family: &FontFamily, script: Script) -> bool {
+    let Some(fonts) = definitions.families.get(family) else {
+        return false;
+    };
+    fonts
+        .iter()
+        .filter_map(|name| definitions.font_data.get(name))
+        .filter_map(|data| ttf_parser::Face::parse(&data.font, data.index).ok())
+        .any(|face| script.sample().chars().all(|c| face.glyph_index(c).is_some()))
+}
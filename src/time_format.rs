@@ -0,0 +1,333 @@
+use std::time::Duration;
+
+use egui::{Response, Ui, Widget};
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::number_format::NumberFormat;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+/// Relative times older than this are shown as a date.
+const MAX_RELATIVE: u64 = 30 * DAY;
+
+/// How [`TimeFormat::format_timestamp`] writes a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimestampStyle {
+    /// `2024-05-01T12:34:56Z`, or `2024-05-01T14:34:56+02:00` with a UTC offset.
+    Iso8601,
+
+    /// `2024-05-01 12:34:56`
+    DateTime,
+
+    /// `2024-05-01`
+    Date,
+
+    /// `12:34:56`
+    Time,
+}
+
+/// Formatting of durations, relative times and timestamps, writing numbers with a
+/// [`NumberFormat`].
+///
+/// ```
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use egui_ui_refresh::time_format::{TimeFormat, TimestampStyle};
+/// let format = TimeFormat::new();
+/// assert_eq!(format.format_duration(Duration::from_micros(1500)), "1.5 ms");
+/// assert_eq!(format.format_duration(Duration::from_secs(200)), "3 min 20 s");
+///
+/// let now = UNIX_EPOCH + Duration::from_secs(1_714_566_896);
+/// assert_eq!(format.format_relative(now - Duration::from_secs(190), now), "3 min ago");
+/// assert_eq!(format.format_relative(now + Duration::from_secs(7200), now), "in 2 h");
+///
+/// assert_eq!(format.format_timestamp(now), "2024-05-01T12:34:56Z");
+/// let paris = format.with_utc_offset(2 * 3600).with_timestamp_style(TimestampStyle::DateTime);
+/// assert_eq!(paris.format_timestamp(now), "2024-05-01 14:34:56");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeFormat {
+    /// Format of the numbers of durations.
+    pub number_format: NumberFormat,
+
+    /// Offset of the local time from UTC, in seconds, for timestamps.
+    pub utc_offset: i32,
+
+    pub timestamp_style: TimestampStyle,
+}
+
+impl TimeFormat {
+    /// UTC ISO 8601 timestamps and [`NumberFormat::DEFAULT`], see
+    /// [`crate::RefreshedTheme::time_format`] to use the number format of the theme instead.
+    pub const fn new() -> Self {
+        Self {
+            number_format: NumberFormat::DEFAULT,
+            utc_offset: 0,
+            timestamp_style: TimestampStyle::Iso8601,
+        }
+    }
+
+    #[inline]
+    pub const fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Offset of the local time from UTC, in seconds (e.g. `3600` for UTC+1).
+    #[inline]
+    pub const fn with_utc_offset(mut self, utc_offset: i32) -> Self {
+        self.utc_offset = utc_offset;
+        self
+    }
+
+    #[inline]
+    pub const fn with_timestamp_style(mut self, timestamp_style: TimestampStyle) -> Self {
+        self.timestamp_style = timestamp_style;
+        self
+    }
+
+    /// Format a duration in the unit that suits its length: `350 ns`, `12.5 ms`, `4.25 s`,
+    /// `3 min 20 s`, `2 h 5 min` or `3 d 4 h`.
+    pub fn format_duration(&self, duration: Duration) -> String {
+        let secs = duration.as_secs();
+        if secs >= DAY {
+            self.two_units(secs, DAY, "d", HOUR, "h")
+        } else if secs >= HOUR {
+            self.two_units(secs, HOUR, "h", MINUTE, "min")
+        } else if secs >= MINUTE {
+            self.two_units(secs, MINUTE, "min", 1, "s")
+        } else {
+            let nanos = duration.as_nanos() as f64;
+            let units = [(1.0, "ns"), (1e3, "µs"), (1e6, "ms"), (1e9, "s")];
+            for (scale, unit) in units {
+                let (value, decimals) = round_to_three_digits(nanos / scale);
+                if unit == "s" && value >= MINUTE as f64 {
+                    // 59.96 s is shown as 1 min rather than 60.0 s
+                    return self.format_duration(Duration::from_secs(MINUTE));
+                }
+                if value < 1000.0 || unit == "s" {
+                    let value = self
+                        .number_format
+                        .format_with_decimals_in_range(value, 0..=decimals);
+                    return format!("{value} {unit}");
+                }
+            }
+            unreachable!("seconds are the last unit")
+        }
+    }
+
+    /// `3 min 20 s`, the smaller unit being omitted when zero.
+    fn two_units(
+        &self,
+        secs: u64,
+        large: u64,
+        large_unit: &str,
+        small: u64,
+        small_unit: &str,
+    ) -> String {
+        let large_count = self.number_format.format((secs / large) as f64, 0);
+        let small_count = (secs % large) / small;
+        if small_count == 0 {
+            format!("{large_count} {large_unit}")
+        } else {
+            format!("{large_count} {large_unit} {small_count} {small_unit}")
+        }
+    }
+
+    /// Format `time` relatively to `now`: `just now`, `3 min ago`, `in a moment`, `in 2 h`, or the
+    /// date when more than 30 days away.
+    pub fn format_relative(&self, time: SystemTime, now: SystemTime) -> String {
+        let (elapsed, is_future) = match now.duration_since(time) {
+            Ok(elapsed) => (elapsed.as_secs(), false),
+            Err(err) => (err.duration().as_secs(), true),
+        };
+
+        let amount = if elapsed < MINUTE {
+            return if is_future { "in a moment" } else { "just now" }.to_owned();
+        } else if elapsed < HOUR {
+            format!("{} min", elapsed / MINUTE)
+        } else if elapsed < DAY {
+            format!("{} h", elapsed / HOUR)
+        } else if elapsed < MAX_RELATIVE {
+            format!("{} d", elapsed / DAY)
+        } else {
+            return self
+                .with_timestamp_style(TimestampStyle::Date)
+                .format_timestamp(time);
+        };
+
+        if is_future {
+            format!("in {amount}")
+        } else {
+            format!("{amount} ago")
+        }
+    }
+
+    /// How long [`Self::format_relative`] keeps returning the same text for `time`.
+    pub fn refresh_interval(&self, time: SystemTime, now: SystemTime) -> Duration {
+        let (elapsed, is_future) = match now.duration_since(time) {
+            Ok(elapsed) => (elapsed, false),
+            Err(err) => (err.duration(), true),
+        };
+        let step = match elapsed.as_secs() {
+            secs if secs < HOUR => MINUTE,
+            secs if secs < DAY => HOUR,
+            _ => DAY,
+        };
+        let step = Duration::from_secs(step);
+        let into_step = Duration::from_nanos((elapsed.as_nanos() % step.as_nanos()) as u64);
+
+        if is_future {
+            // The count goes down as soon as the remaining time drops below a multiple of the
+            // step, right away when it is one
+            into_step.max(Duration::from_nanos(1))
+        } else {
+            step - into_step
+        }
+    }
+
+    /// Format a point in time with [`Self::timestamp_style`], in the [`Self::utc_offset`] time
+    /// zone.
+    pub fn format_timestamp(&self, time: SystemTime) -> String {
+        let secs = match time.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.as_secs() as i64,
+            Err(err) => -(err.duration().as_secs_f64().ceil() as i64),
+        };
+        let local = secs + self.utc_offset as i64;
+
+        let (year, month, day) = civil_from_days(local.div_euclid(DAY as i64));
+        let seconds_of_day = local.rem_euclid(DAY as i64);
+        let (hour, minute, second) = (
+            seconds_of_day / HOUR as i64,
+            seconds_of_day % HOUR as i64 / MINUTE as i64,
+            seconds_of_day % MINUTE as i64,
+        );
+
+        let date = format!("{year:04}-{month:02}-{day:02}");
+        let time = format!("{hour:02}:{minute:02}:{second:02}");
+        match self.timestamp_style {
+            TimestampStyle::Iso8601 => {
+                format!("{date}T{time}{}", format_utc_offset(self.utc_offset))
+            }
+            TimestampStyle::DateTime => format!("{date} {time}"),
+            TimestampStyle::Date => date,
+            TimestampStyle::Time => time,
+        }
+    }
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `value` rounded to three significant digits (or more for values of 1000 and above), and its
+/// number of decimals.
+fn round_to_three_digits(value: f64) -> (f64, usize) {
+    for decimals in [2, 1] {
+        let factor = 10f64.powi(decimals as i32);
+        let rounded = (value * factor).round() / factor;
+        // 9.996 rounds to 10.00, which has four digits
+        if rounded < 10f64.powi(3 - decimals as i32) {
+            return (rounded, decimals);
+        }
+    }
+    (value.round(), 0)
+}
+
+/// `Z`, or `±hh:mm`.
+fn format_utc_offset(utc_offset: i32) -> String {
+    if utc_offset == 0 {
+        return "Z".to_owned();
+    }
+    let sign = if utc_offset < 0 { '-' } else { '+' };
+    let minutes = utc_offset.unsigned_abs() / 60;
+    format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Year, month and day of a number of days since 1970-01-01 in the proleptic Gregorian
+/// calendar: <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as u32, day as u32)
+}
+
+/// A label showing how long ago (or in how long) a point in time is, repainting when the text
+/// changes. The timestamp is shown on hover.
+///
+/// The time is a [`web_time::SystemTime`], which is [`std::time::SystemTime`] except on the web
+/// where the standard one isn't available.
+///
+/// ```
+/// # use std::time::{Duration, SystemTime};
+/// # use egui_ui_refresh::time_format::RelativeTimeLabel;
+/// # egui::__run_test_ui(|ui| {
+/// let last_saved = SystemTime::now() - Duration::from_secs(300);
+/// ui.add(RelativeTimeLabel::new(last_saved));
+/// # });
+/// ```
+#[must_use = "You should put this widget in a ui with `ui.add(widget);`"]
+pub struct RelativeTimeLabel {
+    time: SystemTime,
+    format: TimeFormat,
+    prefix: Option<String>,
+}
+
+impl RelativeTimeLabel {
+    pub fn new(time: SystemTime) -> Self {
+        Self {
+            time,
+            format: TimeFormat::new(),
+            prefix: None,
+        }
+    }
+
+    #[inline]
+    pub fn format(mut self, format: TimeFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Text before the relative time, e.g. `"Saved "`.
+    #[inline]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+}
+
+impl Widget for RelativeTimeLabel {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            time,
+            format,
+            prefix,
+        } = self;
+        let now = SystemTime::now();
+
+        let relative = format.format_relative(time, now);
+        let text = match prefix {
+            Some(prefix) => format!("{prefix}{relative}"),
+            None => relative,
+        };
+        ui.ctx()
+            .request_repaint_after(format.refresh_interval(time, now));
+
+        ui.label(text).on_hover_text(format.format_timestamp(time))
+    }
+}
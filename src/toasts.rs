@@ -2,15 +2,9 @@
 
 use std::collections::HashMap;
 
-use egui::Color32;
-
 use crate::icons;
-use crate::icons::icon;
 
-pub const INFO_COLOR: Color32 = Color32::from_rgb(0, 155, 255);
-pub const WARNING_COLOR: Color32 = Color32::from_rgb(255, 212, 0);
-pub const ERROR_COLOR: Color32 = Color32::from_rgb(255, 32, 0);
-pub const SUCCESS_COLOR: Color32 = Color32::from_rgb(0, 255, 32);
+pub use crate::{ERROR_COLOR, INFO_COLOR, SUCCESS_COLOR, WARNING_COLOR};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ToastKind {
@@ -147,7 +141,7 @@ fn default_toast_contents(ui: &mut egui::Ui, toast: &Toast) -> egui::Response {
                         ToastKind::Success => (icons::CHECK, SUCCESS_COLOR),
                         _ => (icons::INFO, INFO_COLOR),
                     };
-                    ui.label(icon(the_icon, 16.0).color(icon_color));
+                    ui.add(the_icon.image().tint(icon_color));
                 }
                 ui.label(toast.text.clone());
             })
@@ -97,7 +97,7 @@ pub fn top_bar(egui_ctx: &Context, custom_decorations: bool) {
                 ui.set_height(top_bar_style.height);
                 ui.add_space(top_bar_style.indent);
 
-                ui.menu_button("File", |ui| {} /*file_menu(ui, &self.command_sender)*/);
+                ui.menu_button("File", |_ui| {} /*file_menu(ui, &self.command_sender)*/);
 
                 top_bar_ui(ui, custom_decorations);
             });
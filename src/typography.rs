@@ -0,0 +1,193 @@
+use egui::text::{LayoutJob, TextFormat};
+use egui::{FontFamily, FontId, RichText, TextStyle};
+
+use crate::fonts::{
+    font_family_bold, font_family_bold_italic, font_family_italic, font_family_medium,
+    font_family_semibold,
+};
+use crate::RefreshedTheme;
+
+/// The named text styles of the type scale, registered as [`TextStyle::Name`] by
+/// [`RefreshedTheme::apply`].
+///
+/// Like headings, a style whose family isn't defined by the fonts of the context uses the
+/// proportional family.
+///
+/// ```no_run
+/// # use egui_ui_refresh::typography::Typography;
+/// # egui::__run_test_ui(|ui| {
+/// ui.label(Typography::Caption.rich_text("Last updated yesterday"));
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Typography {
+    Title,
+    Subtitle,
+    Caption,
+    Overline,
+    Code,
+    Small,
+}
+
+impl Typography {
+    pub const ALL: [Self; 6] = [
+        Self::Title,
+        Self::Subtitle,
+        Self::Caption,
+        Self::Overline,
+        Self::Code,
+        Self::Small,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Subtitle => "subtitle",
+            Self::Caption => "caption",
+            Self::Overline => "overline",
+            Self::Code => "code",
+            Self::Small => "small",
+        }
+    }
+
+    pub fn text_style(self) -> TextStyle {
+        TextStyle::Name(self.name().into())
+    }
+
+    /// Text with this style and the line height and letter spacing of the global theme.
+    pub fn rich_text(self, text: impl Into<String>) -> RichText {
+        RefreshedTheme::get().rich_text(self, text)
+    }
+}
+
+/// Font families usable in a [`TypeStyle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeFamily {
+    Regular,
+    Italic,
+    Medium,
+    SemiBold,
+    Bold,
+    BoldItalic,
+    Monospace,
+}
+
+impl TypeFamily {
+    pub fn font_family(self) -> FontFamily {
+        match self {
+            Self::Regular => FontFamily::Proportional,
+            Self::Italic => font_family_italic(),
+            Self::Medium => font_family_medium(),
+            Self::SemiBold => font_family_semibold(),
+            Self::Bold => font_family_bold(),
+            Self::BoldItalic => font_family_bold_italic(),
+            Self::Monospace => FontFamily::Monospace,
+        }
+    }
+}
+
+/// Size, family and line height of a [`Typography`] style.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypeStyle {
+    pub size: f32,
+    pub family: TypeFamily,
+    pub line_height: f32,
+    pub letter_spacing: f32,
+}
+
+impl TypeStyle {
+    pub const fn new(size: f32, family: TypeFamily, line_height: f32) -> Self {
+        Self {
+            size,
+            family,
+            line_height,
+            letter_spacing: 0.0,
+        }
+    }
+
+    #[inline]
+    pub const fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
+    pub fn font_id(&self) -> FontId {
+        FontId::new(self.size, self.family.font_family())
+    }
+}
+
+/// The type scale of a theme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypeScale {
+    pub title: TypeStyle,
+    pub subtitle: TypeStyle,
+    pub caption: TypeStyle,
+    pub overline: TypeStyle,
+    pub code: TypeStyle,
+    pub small: TypeStyle,
+}
+
+impl TypeScale {
+    pub const fn new() -> Self {
+        Self {
+            title: TypeStyle::new(20.0, TypeFamily::SemiBold, 28.0),
+            subtitle: TypeStyle::new(16.0, TypeFamily::Medium, 22.0),
+            caption: TypeStyle::new(11.0, TypeFamily::Regular, 14.0),
+            overline: TypeStyle::new(10.0, TypeFamily::Medium, 14.0).letter_spacing(0.5),
+            code: TypeStyle::new(12.0, TypeFamily::Monospace, 16.0),
+            small: TypeStyle::new(10.0, TypeFamily::Regular, 13.0),
+        }
+    }
+
+    pub fn get(&self, typography: Typography) -> &TypeStyle {
+        match typography {
+            Typography::Title => &self.title,
+            Typography::Subtitle => &self.subtitle,
+            Typography::Caption => &self.caption,
+            Typography::Overline => &self.overline,
+            Typography::Code => &self.code,
+            Typography::Small => &self.small,
+        }
+    }
+}
+
+impl Default for TypeScale {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Layout `text` so that its digits have a fixed advance, and numbers don't jitter when their
+/// value changes.
+///
+/// Inter has proportional digits, so the digits are taken from the monospace family at the same
+/// size, the rest of the text keeps the font of `format`.
+///
+/// ```
+/// # use egui::text::TextFormat;
+/// # use egui_ui_refresh::typography::tabular_layout_job;
+/// let job = tabular_layout_job("Total: 1,024 items", TextFormat::default());
+/// assert_eq!(job.sections.len(), 5);
+/// # egui::__run_test_ui(|ui| {
+/// ui.label(job.clone());
+/// # });
+/// ```
+pub fn tabular_layout_job(text: &str, format: TextFormat) -> LayoutJob {
+    let digits_format = TextFormat {
+        font_id: FontId::new(format.font_id.size, FontFamily::Monospace),
+        ..format.clone()
+    };
+
+    let mut job = LayoutJob::default();
+    let mut start = 0;
+    while start < text.len() {
+        let is_digit = text[start..].starts_with(|c: char| c.is_ascii_digit());
+        let len = text[start..]
+            .find(|c: char| c.is_ascii_digit() != is_digit)
+            .unwrap_or(text.len() - start);
+        let run_format = if is_digit { &digits_format } else { &format };
+        job.append(&text[start..start + len], 0.0, run_format.clone());
+        start += len;
+    }
+    job
+}
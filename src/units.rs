@@ -0,0 +1,262 @@
+use std::ops::RangeInclusive;
+
+use egui::{DragValue, Slider};
+
+use crate::number_format::NumberFormat;
+
+/// The kind of quantity a value is, and the unit it is stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// A size in bytes, shown in `B`, `KiB`, `MiB`, … (powers of 1024).
+    Bytes,
+
+    /// A duration in seconds, shown in `ns`, `µs`, `ms`, `s`, `min` or `h`.
+    Seconds,
+
+    /// An angle in degrees, shown with `°`.
+    Degrees,
+
+    /// A ratio where `1.0` is 100 %, shown in percents.
+    Percent,
+}
+
+impl Unit {
+    /// The scales of the unit from the smallest to the largest, with the factor from the
+    /// displayed value to the stored one.
+    fn scales(self) -> &'static [Scale] {
+        const BYTES: &[Scale] = &[
+            Scale::new("B", 1.0),
+            Scale::new("KiB", 1024.0),
+            Scale::new("MiB", 1024.0 * 1024.0),
+            Scale::new("GiB", 1024.0 * 1024.0 * 1024.0),
+            Scale::new("TiB", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+            Scale::new("PiB", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ];
+        const SECONDS: &[Scale] = &[
+            Scale::new("ns", 1e-9),
+            Scale::new("µs", 1e-6),
+            Scale::new("ms", 1e-3),
+            Scale::new("s", 1.0),
+            Scale::new("min", 60.0),
+            Scale::new("h", 3600.0),
+        ];
+        const DEGREES: &[Scale] = &[Scale::new("°", 1.0).without_space()];
+        const PERCENT: &[Scale] = &[Scale::new("%", 0.01).without_space()];
+
+        match self {
+            Self::Bytes => BYTES,
+            Self::Seconds => SECONDS,
+            Self::Degrees => DEGREES,
+            Self::Percent => PERCENT,
+        }
+    }
+
+    /// Index of the scale assumed for zero, and when parsing a number without suffix.
+    fn base_scale(self) -> usize {
+        self.scales()
+            .iter()
+            .position(|scale| scale.factor == 1.0)
+            .unwrap_or(0)
+    }
+
+    /// Other spellings accepted when parsing.
+    fn aliases(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Self::Bytes => &[("bytes", 1.0)],
+            Self::Seconds => &[("us", 1e-6), ("sec", 1.0)],
+            Self::Degrees => &[("deg", 1.0)],
+            Self::Percent => &[],
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Scale {
+    suffix: &'static str,
+    factor: f64,
+    space: bool,
+}
+
+impl Scale {
+    const fn new(suffix: &'static str, factor: f64) -> Self {
+        Self {
+            suffix,
+            factor,
+            space: true,
+        }
+    }
+
+    const fn without_space(mut self) -> Self {
+        self.space = false;
+        self
+    }
+}
+
+/// Formatting and parsing of values with a [`Unit`], on top of a [`NumberFormat`].
+///
+/// Values are shown in the largest scale keeping them at least 1 once rounded (`1.5 KiB` rather
+/// than `1 536 B`, `1 KiB` rather than `1 024 B` for 1023.9 bytes), and parsed back from any of the scales of the unit, a number without suffix being
+/// in the base unit (`B`, `s`, `°` or `%`).
+///
+/// ```
+/// # use egui_ui_refresh::units::{Unit, UnitFormat};
+/// let bytes = UnitFormat::new(Unit::Bytes);
+/// assert_eq!(bytes.format(1536.0, 0..=0), "1.5 KiB");
+/// assert_eq!(bytes.parse("2 MiB"), Some(2.0 * 1024.0 * 1024.0));
+///
+/// let seconds = UnitFormat::new(Unit::Seconds);
+/// assert_eq!(seconds.format(0.25, 0..=3), "250 ms");
+/// assert_eq!(seconds.parse("1.5 min"), Some(90.0));
+///
+/// assert_eq!(UnitFormat::new(Unit::Degrees).format(-45.0, 0..=1), "−45°");
+///
+/// let percent = UnitFormat::new(Unit::Percent);
+/// assert_eq!(percent.format(0.5, 0..=2), "50%");
+/// assert_eq!(percent.parse("12.5"), Some(0.125));
+/// ```
+///
+/// Attach it to a [`DragValue`] or a [`Slider`]:
+///
+/// ```
+/// # use egui_ui_refresh::units::{Unit, UnitFormat};
+/// # egui::__run_test_ui(|ui| {
+/// # let mut cache_size = 64.0 * 1024.0 * 1024.0;
+/// # let mut timeout = 0.5;
+/// ui.add(UnitFormat::new(Unit::Bytes).drag_value(egui::DragValue::new(&mut cache_size)));
+/// ui.add(UnitFormat::new(Unit::Seconds).slider(egui::Slider::new(&mut timeout, 0.0..=10.0)));
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitFormat {
+    pub unit: Unit,
+
+    /// Format of the number before the unit.
+    ///
+    /// Its SI suffixes are ignored, the unit choosing its own scale.
+    pub number_format: NumberFormat,
+}
+
+impl UnitFormat {
+    /// A unit format with [`NumberFormat::DEFAULT`], see [`crate::RefreshedTheme::unit_format`]
+    /// to use the number format of the theme instead.
+    pub const fn new(unit: Unit) -> Self {
+        Self {
+            unit,
+            number_format: NumberFormat::DEFAULT,
+        }
+    }
+
+    #[inline]
+    pub const fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Format `value`, given in the stored unit, in the best scale.
+    ///
+    /// `decimal_range` is the range of decimals of the stored value, as passed to
+    /// [`DragValue::custom_formatter`], and is shifted to keep a similar precision in the chosen
+    /// scale.
+    pub fn format(&self, value: f64, decimal_range: RangeInclusive<usize>) -> String {
+        let scales = self.unit.scales();
+        let mut value = value;
+        let mut index = self.scale_for(value);
+        loop {
+            let scale = &scales[index];
+            let shift = scale.factor.log10().round() as isize;
+            let (min_decimals, max_decimals) = if shift == 0 {
+                (*decimal_range.start(), *decimal_range.end())
+            } else {
+                let max_decimals = (*decimal_range.end() as isize + shift).clamp(0, 16) as usize;
+                (0, max_decimals)
+            };
+
+            let number = self
+                .number_format()
+                .format_with_decimals_in_range(value / scale.factor, min_decimals..=max_decimals);
+
+            // Rounding may reach the next scale, e.g. 1023.9 B to 1 024 B, which is 1 KiB
+            let rounded = self.number_format().parse(&number);
+            if let (Some(next), Some(rounded)) = (scales.get(index + 1), rounded) {
+                if rounded.abs() >= (next.factor / scale.factor).round() {
+                    value = rounded * scale.factor;
+                    index += 1;
+                    continue;
+                }
+            }
+
+            let space = if scale.space { " " } else { "" };
+            return format!("{number}{space}{}", scale.suffix);
+        }
+    }
+
+    /// Parse a value written in any of the scales of the unit, returning it in the stored unit.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        let suffixes = self
+            .unit
+            .scales()
+            .iter()
+            .map(|scale| (scale.suffix, scale.factor))
+            .chain(self.unit.aliases().iter().copied());
+
+        // Longest match first, so that `ms` isn't read as `s`
+        let mut best: Option<(&str, f64)> = None;
+        for (suffix, factor) in suffixes {
+            let is_longer = best.map_or(true, |(best, _)| suffix.len() > best.len());
+            if is_longer && ends_with_ignore_case(text, suffix) {
+                best = Some((suffix, factor));
+            }
+        }
+
+        let (number, factor) = match best {
+            Some((suffix, factor)) => (&text[..text.len() - suffix.len()], factor),
+            None => (text, self.unit.scales()[self.unit.base_scale()].factor),
+        };
+        self.number_format()
+            .parse(number)
+            .map(|value| value * factor)
+    }
+
+    /// A parser for [`DragValue::custom_parser`] and [`Slider::custom_parser`].
+    pub fn parser(self) -> impl 'static + Fn(&str) -> Option<f64> {
+        move |text| self.parse(text)
+    }
+
+    /// Show the value of `drag_value` with this unit, and accept it in any scale when edited.
+    pub fn drag_value(self, drag_value: DragValue<'_>) -> DragValue<'_> {
+        drag_value
+            .custom_formatter(move |value, decimal_range| self.format(value, decimal_range))
+            .custom_parser(self.parser())
+    }
+
+    /// Show the value of `slider` with this unit, and accept it in any scale when edited.
+    pub fn slider(self, slider: Slider<'_>) -> Slider<'_> {
+        slider
+            .custom_formatter(move |value, decimal_range| self.format(value, decimal_range))
+            .custom_parser(self.parser())
+    }
+
+    fn number_format(&self) -> NumberFormat {
+        self.number_format.with_si_suffixes(false)
+    }
+
+    /// Index of the largest scale keeping `value` at least 1.
+    fn scale_for(&self, value: f64) -> usize {
+        let magnitude = value.abs();
+        if magnitude == 0.0 || !magnitude.is_finite() {
+            return self.unit.base_scale();
+        }
+        self.unit
+            .scales()
+            .iter()
+            .rposition(|scale| magnitude >= scale.factor)
+            .unwrap_or(0)
+    }
+}
+
+fn ends_with_ignore_case(text: &str, suffix: &str) -> bool {
+    text.len() >= suffix.len()
+        && text.is_char_boundary(text.len() - suffix.len())
+        && text[text.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+}
@@ -0,0 +1,192 @@
+//! Lays out lines mixing text, icons and emojis, and checks that they are centered on the cap
+//! height of the text at every size, and that they render like the snapshots in
+//! `tests/snapshots`.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to write the snapshots after an intended change of the fonts or
+//! their tweaks.
+
+#![cfg(feature = "fonts")]
+
+use std::path::PathBuf;
+
+use egui::epaint::text::{Fonts, LayoutJob, TextFormat};
+use egui::{Color32, FontId, Galley};
+
+use egui_ui_refresh::fonts::{font_icons, fonts, text_families};
+use egui_ui_refresh::icons;
+
+const SIZES: [f32; 8] = [10.0, 11.0, 12.0, 14.0, 16.0, 20.0, 24.0, 32.0];
+
+const PIXELS_PER_POINT: [f32; 2] = [1.0, 2.0];
+
+/// Vertical center of the ink of each glyph of a line made of `sections`.
+fn ink_centers(fonts: &Fonts, sections: &[(&str, FontId)]) -> Vec<f32> {
+    let mut job = LayoutJob::default();
+    for (text, font_id) in sections {
+        job.append(text, 0.0, TextFormat::simple(font_id.clone(), Color32::WHITE));
+    }
+    let galley = fonts.layout_job(job);
+    galley.rows[0]
+        .glyphs
+        .iter()
+        .map(|glyph| glyph.pos.y + glyph.uv_rect.offset.y + 0.5 * glyph.uv_rect.size.y)
+        .collect()
+}
+
+fn assert_aligned(what: &str, size: f32, pixels_per_point: f32, reference: f32, center: f32) {
+    // Glyphs are snapped to physical pixels
+    let tolerance = 1.0 / pixels_per_point;
+    assert!(
+        (reference - center).abs() <= tolerance,
+        "{what} at size {size} (x{pixels_per_point}) is off by {} points",
+        center - reference
+    );
+}
+
+#[test]
+fn icons_are_centered_on_text() {
+    let icons = [icons::CLOSE, icons::INFO, icons::WARNING_CIRCLE];
+
+    for pixels_per_point in PIXELS_PER_POINT {
+        let fonts = Fonts::new(pixels_per_point, 8 * 1024, fonts());
+        for size in SIZES {
+            for family in &text_families() {
+                let codepoints: String = icons.iter().map(|icon| icon.codepoint).collect();
+                let centers = ink_centers(
+                    &fonts,
+                    &[
+                        ("H", FontId::new(size, family.clone())),
+                        (&codepoints, font_icons(size)),
+                    ],
+                );
+                for (icon, center) in icons.iter().zip(&centers[1..]) {
+                    assert_aligned(
+                        &format!("{icon:?} next to {family}"),
+                        size,
+                        pixels_per_point,
+                        centers[0],
+                        *center,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn emojis_are_centered_on_text() {
+    for pixels_per_point in PIXELS_PER_POINT {
+        let fonts = Fonts::new(pixels_per_point, 8 * 1024, fonts());
+        for size in SIZES {
+            for family in text_families() {
+                let centers = ink_centers(&fonts, &[("H🙂", FontId::new(size, family.clone()))]);
+                assert_aligned(
+                    &format!("🙂 in {family}"),
+                    size,
+                    pixels_per_point,
+                    centers[0],
+                    centers[1],
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn monospace_is_centered_on_proportional() {
+    for pixels_per_point in PIXELS_PER_POINT {
+        let fonts = Fonts::new(pixels_per_point, 8 * 1024, fonts());
+        for size in SIZES {
+            let centers = ink_centers(
+                &fonts,
+                &[
+                    ("H", FontId::proportional(size)),
+                    ("H", FontId::monospace(size)),
+                ],
+            );
+            assert_aligned("Jetbrains Mono", size, pixels_per_point, centers[0], centers[1]);
+        }
+    }
+}
+
+/// Draw `galley` as a grayscale image, copying the glyphs from the font atlas like the renderer.
+fn render(fonts: &Fonts, galley: &Galley, pixels_per_point: f32) -> ([usize; 2], Vec<u8>) {
+    let atlas = fonts.image();
+    let size = [
+        (galley.size().x * pixels_per_point).ceil() as usize,
+        (galley.size().y * pixels_per_point).ceil() as usize,
+    ];
+    let mut pixels = vec![0u8; size[0] * size[1]];
+    for glyph in galley.rows.iter().flat_map(|row| &row.glyphs) {
+        let uv = glyph.uv_rect;
+        let left = ((glyph.pos.x + uv.offset.x) * pixels_per_point).round() as isize;
+        let top = ((glyph.pos.y + uv.offset.y) * pixels_per_point).round() as isize;
+        for v in uv.min[1]..uv.max[1] {
+            for u in uv.min[0]..uv.max[0] {
+                let x = left + (u - uv.min[0]) as isize;
+                let y = top + (v - uv.min[1]) as isize;
+                if (0..size[0] as isize).contains(&x) && (0..size[1] as isize).contains(&y) {
+                    let coverage = atlas.pixels[v as usize * atlas.size[0] + u as usize];
+                    let pixel = &mut pixels[y as usize * size[0] + x as usize];
+                    *pixel = pixel.saturating_add((coverage * 255.0).round() as u8);
+                }
+            }
+        }
+    }
+    (size, pixels)
+}
+
+/// Compare `pixels` to the PGM snapshot `name`, or write it with `UPDATE_SNAPSHOTS` set.
+fn assert_snapshot(name: &str, size: [usize; 2], pixels: &[u8]) {
+    let mut image = format!("P5\n{} {}\n255\n", size[0], size[1]).into_bytes();
+    image.extend_from_slice(pixels);
+
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+    let path = dir.join(format!("{name}.pgm"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, &image).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read(&path).unwrap_or_else(|err| {
+        panic!(
+            "{} can't be read ({err}), run with UPDATE_SNAPSHOTS=1",
+            path.display()
+        )
+    });
+    if expected != image {
+        let actual = dir.join(format!("{name}.actual.pgm"));
+        std::fs::write(&actual, &image).unwrap();
+        panic!(
+            "{name} doesn't match its snapshot, see {}, run with UPDATE_SNAPSHOTS=1 if it's expected",
+            actual.display()
+        );
+    }
+}
+
+#[test]
+fn mixed_lines_match_snapshots() {
+    let codepoints: String = [icons::CLOSE, icons::INFO, icons::WARNING_CIRCLE]
+        .iter()
+        .map(|icon| icon.codepoint)
+        .collect();
+
+    for pixels_per_point in PIXELS_PER_POINT {
+        let fonts = Fonts::new(pixels_per_point, 8 * 1024, fonts());
+        let mut job = LayoutJob::default();
+        for family in text_families() {
+            for size in [11.0, 14.0, 20.0] {
+                let text = TextFormat::simple(FontId::new(size, family.clone()), Color32::WHITE);
+                let icons = TextFormat::simple(font_icons(size), Color32::WHITE);
+                job.append("Hxg 1024 ", 0.0, text.clone());
+                job.append(&codepoints, 0.0, icons);
+                job.append(" 🙂\n", 0.0, text);
+            }
+        }
+        let galley = fonts.layout_job(job);
+
+        let (size, pixels) = render(&fonts, &galley, pixels_per_point);
+        assert_snapshot(&format!("mixed_lines_x{pixels_per_point}"), size, &pixels);
+    }
+}
@@ -0,0 +1,30 @@
+//! Inserts application fonts in the bundled font families.
+
+#![cfg(feature = "fonts")]
+
+use egui::{FontData, FontFamily};
+
+use egui_ui_refresh::fonts::{FontPosition, FontsBuilder, INTER_REGULAR, NOTO_EMOJI, PHOSPHOR};
+
+#[test]
+fn after_primary_follows_the_bundled_font() {
+    let proportional = [FontFamily::Proportional];
+    let fonts = FontsBuilder::new()
+        .add_font(
+            "Brand",
+            FontData::from_static(&[]),
+            &proportional,
+            FontPosition::First,
+        )
+        .add_font(
+            "NotoSansJP",
+            FontData::from_static(&[]),
+            &proportional,
+            FontPosition::AfterPrimary,
+        )
+        .build();
+    assert_eq!(
+        fonts.families[&FontFamily::Proportional],
+        ["Brand", INTER_REGULAR, "NotoSansJP", NOTO_EMOJI, PHOSPHOR]
+    );
+}
@@ -0,0 +1,125 @@
+//! Paints icons in a headless [`egui::Context`], and checks where their glyphs end up and how
+//! icon buttons are tinted.
+
+#![cfg(feature = "fonts")]
+
+use egui::epaint::{ClippedShape, Shape, TextShape};
+use egui::{
+    CentralPanel, Color32, Context, Event, LayerId, PointerButton, Pos2, RawInput, Rect, Vec2,
+};
+
+use egui_ui_refresh::icons::{self, Icon};
+use egui_ui_refresh::RefreshedTheme;
+
+fn context() -> Context {
+    let ctx = Context::default();
+    RefreshedTheme::init_default().apply_with_fonts(&ctx, egui_ui_refresh::fonts::fonts());
+    // Fonts are loaded at the start of the next frame
+    let _ = ctx.run(RawInput::default(), |_| {});
+    ctx
+}
+
+fn text_shapes(shapes: Vec<ClippedShape>) -> Vec<TextShape> {
+    fn collect(shape: Shape, texts: &mut Vec<TextShape>) {
+        match shape {
+            Shape::Text(text) => texts.push(text),
+            Shape::Vec(shapes) => shapes.into_iter().for_each(|shape| collect(shape, texts)),
+            _ => {}
+        }
+    }
+    let mut texts = Vec::new();
+    for ClippedShape { shape, .. } in shapes {
+        collect(shape, &mut texts);
+    }
+    texts
+}
+
+/// Bounds of the ink of the single glyph of `text`.
+fn ink_rect(text: &TextShape) -> Rect {
+    let glyph = &text.galley.rows[0].glyphs[0];
+    Rect::from_min_size(
+        text.pos + glyph.pos.to_vec2() + glyph.uv_rect.offset,
+        glyph.uv_rect.size,
+    )
+}
+
+#[test]
+fn paint_centers_the_glyph_bounds() {
+    let ctx = context();
+    let icons = [
+        icons::CLOSE,
+        icons::INFO,
+        icons::WARNING,
+        icons::CHECK,
+        icons::EXCLAMATION_MARK,
+    ];
+    for pixels_per_point in [1.0, 2.0] {
+        ctx.set_pixels_per_point(pixels_per_point);
+        for size in [12.0, 16.0, 24.0, 40.0] {
+            for icon in icons {
+                let rect = Rect::from_min_size(Pos2::new(10.0, 20.0), Vec2::splat(size));
+                let output = ctx.run(RawInput::default(), |ctx| {
+                    let painter = ctx.layer_painter(LayerId::background());
+                    icon.with_size(size).paint(&painter, rect, Color32::WHITE);
+                });
+                let texts = text_shapes(output.shapes);
+                let offset = ink_rect(&texts[0]).center() - rect.center();
+
+                // Snapped to physical pixels
+                let tolerance = 1.0 / pixels_per_point;
+                assert!(
+                    offset.x.abs() <= tolerance && offset.y.abs() <= tolerance,
+                    "{:?} at {size} pt, {pixels_per_point} px/pt: off center by {offset:?}",
+                    icon.codepoint
+                );
+            }
+        }
+    }
+}
+
+/// Runs a frame with an icon button at the top left corner, and returns its color.
+fn button_frame(ctx: &Context, events: Vec<Event>, icon: Icon) -> Color32 {
+    let output = ctx.run(
+        RawInput {
+            events,
+            ..Default::default()
+        },
+        |ctx| {
+            CentralPanel::default().show(ctx, |ui| ui.add(icon.button()));
+        },
+    );
+    text_shapes(output.shapes)[0].fallback_color
+}
+
+#[test]
+fn button_is_tinted_by_its_state() {
+    let ctx = context();
+    let theme = RefreshedTheme::get();
+    let icon = icons::CLOSE.with_size(24.0);
+
+    assert_eq!(button_frame(&ctx, vec![], icon), theme.inactive);
+
+    // The central panel has a margin of 8 points
+    let pos = Pos2::new(20.0, 20.0);
+    let button = |pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+    assert_eq!(
+        button_frame(&ctx, vec![Event::PointerMoved(pos)], icon),
+        theme.hovered
+    );
+    assert_eq!(button_frame(&ctx, vec![button(true)], icon), theme.active);
+    assert_eq!(button_frame(&ctx, vec![button(false)], icon), theme.hovered);
+
+    assert_eq!(
+        button_frame(
+            &ctx,
+            vec![Event::PointerMoved(Pos2::new(200.0, 200.0))],
+            icon
+        ),
+        theme.inactive
+    );
+}
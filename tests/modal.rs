@@ -0,0 +1,721 @@
+//! Runs frames of a headless [`egui::Context`] with a modal open over other widgets.
+
+#![cfg(all(feature = "modal", feature = "fonts"))]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use egui::{CentralPanel, Context, Event, Key, PointerButton, Pos2, RawInput, Rect, Response};
+
+use egui_ui_refresh::async_modal::AsyncModalHandler;
+use egui_ui_refresh::dialogs::{ConfirmDialog, DialogResult, PromptDialog};
+use egui_ui_refresh::modal::{
+    DismissPolicy, DismissReason, FooterAction, Modal, ModalAnimation, ModalFooter, ModalHandler,
+    ModalPosition,
+};
+use egui_ui_refresh::RefreshedTheme;
+
+fn screen() -> Rect {
+    Rect::from_min_size(Pos2::ZERO, egui::vec2(800.0, 600.0))
+}
+
+fn input(events: Vec<Event>) -> RawInput {
+    RawInput {
+        screen_rect: Some(screen()),
+        events,
+        ..Default::default()
+    }
+}
+
+fn click(pos: Pos2) -> Vec<Vec<Event>> {
+    let button = |pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+    vec![
+        vec![Event::PointerMoved(pos)],
+        vec![button(true)],
+        vec![button(false)],
+    ]
+}
+
+/// Runs a frame with a button in the background (at the bottom of the screen, outside the
+/// modal) and, if `modal` is set, the modal on top. Returns the button response and whether the
+/// modal is still open.
+fn frame(ctx: &Context, events: Vec<Event>, modal: Option<&mut Modal>) -> (Response, bool) {
+    let mut button = None;
+    let mut open = true;
+    let _ = ctx.run(input(events), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.add_space(500.0);
+            button = Some(ui.button("Behind"));
+        });
+        if let Some(modal) = modal {
+            open = modal.ui(ctx, |ui, _| ui.label("Content")).open;
+        }
+    });
+    (button.unwrap(), open)
+}
+
+fn context() -> Context {
+    let ctx = Context::default();
+    RefreshedTheme::init_default().apply_with_fonts(&ctx, egui_ui_refresh::fonts::fonts());
+    // Fonts are loaded at the start of the next frame
+    let _ = ctx.run(input(vec![]), |_| {});
+    ctx
+}
+
+#[test]
+fn widgets_underneath_are_not_clickable() {
+    let ctx = context();
+    let mut modal = Modal::new("Blocking").close_on_outside_click(false);
+
+    let (button, _) = frame(&ctx, vec![], Some(&mut modal));
+    for events in click(button.rect.center()) {
+        let (button, open) = frame(&ctx, events, Some(&mut modal));
+        assert!(!button.hovered());
+        assert!(!button.clicked());
+        assert!(open);
+    }
+}
+
+#[test]
+fn outside_click_closes() {
+    let ctx = context();
+    let mut modal = Modal::new("Closing");
+
+    let (button, _) = frame(&ctx, vec![], Some(&mut modal));
+    let open = click(button.rect.center())
+        .into_iter()
+        .map(|events| frame(&ctx, events, Some(&mut modal)).1)
+        .collect::<Vec<_>>();
+    // Once closed, the modal stays closed while its close animation plays
+    assert_eq!(open, vec![true, false, false]);
+}
+
+#[test]
+fn widgets_underneath_lose_focus() {
+    let ctx = context();
+
+    let (button, _) = frame(&ctx, vec![], None);
+    button.request_focus();
+    let (button, _) = frame(&ctx, vec![], None);
+    assert!(button.has_focus());
+
+    let mut modal = Modal::new("Focus");
+    let (button, _) = frame(&ctx, vec![], Some(&mut modal));
+    let (_, _) = frame(&ctx, vec![], Some(&mut modal));
+    assert!(!ctx.memory(|mem| mem.has_focus(button.id)));
+}
+
+fn key_press(key: Key) -> Vec<Event> {
+    vec![Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: Default::default(),
+    }]
+}
+
+#[test]
+fn dismiss_policy_disables_escape() {
+    let ctx = context();
+
+    let mut modal = Modal::new("Escape");
+    frame(&ctx, vec![], Some(&mut modal));
+    assert!(!frame(&ctx, key_press(Key::Escape), Some(&mut modal)).1);
+
+    let mut modal = Modal::new("No escape").dismiss_policy(DismissPolicy {
+        escape: false,
+        ..DismissPolicy::ALL
+    });
+    frame(&ctx, vec![], Some(&mut modal));
+    assert!(frame(&ctx, key_press(Key::Escape), Some(&mut modal)).1);
+}
+
+#[test]
+fn confirm_close_vetoes_dismissal() {
+    let ctx = context();
+    let dirty = Arc::new(AtomicBool::new(true));
+    let reasons = Arc::new(Mutex::new(Vec::new()));
+    let mut modal = Modal::new("Dirty").confirm_close({
+        let dirty = dirty.clone();
+        let reasons = reasons.clone();
+        move |reason| {
+            reasons.lock().unwrap().push(reason);
+            !dirty.load(Ordering::Relaxed)
+        }
+    });
+
+    let (button, _) = frame(&ctx, vec![], Some(&mut modal));
+    assert!(frame(&ctx, key_press(Key::Escape), Some(&mut modal)).1);
+    let open = click(button.rect.center())
+        .into_iter()
+        .all(|events| frame(&ctx, events, Some(&mut modal)).1);
+    assert!(open);
+    assert_eq!(
+        *reasons.lock().unwrap(),
+        vec![DismissReason::Escape, DismissReason::OutsideClick]
+    );
+
+    dirty.store(false, Ordering::Relaxed);
+    assert!(!frame(&ctx, key_press(Key::Escape), Some(&mut modal)).1);
+}
+
+/// Runs a frame with a parent modal containing a button, and a child modal on top.
+fn stacked_frame(
+    ctx: &Context,
+    events: Vec<Event>,
+    parent: &mut ModalHandler,
+    child: &mut ModalHandler,
+) -> Option<Response> {
+    let mut button = None;
+    let _ = ctx.run(input(events), |ctx| {
+        CentralPanel::default().show(ctx, |_| {});
+        parent.ui(
+            ctx,
+            || Modal::new("Parent").min_width(600.0),
+            |ui, _| {
+                ui.add_space(200.0);
+                button = Some(ui.button("In parent"));
+                child.ui(ui.ctx(), || Modal::new("Child"), |ui, _| ui.label("Child"));
+            },
+        );
+    });
+    button
+}
+
+#[test]
+fn only_the_topmost_modal_is_dismissed() {
+    let ctx = context();
+    let mut parent = ModalHandler::default();
+    let mut child = ModalHandler::default();
+    parent.open();
+    child.open();
+
+    let button = stacked_frame(&ctx, vec![], &mut parent, &mut child).unwrap();
+    stacked_frame(&ctx, vec![], &mut parent, &mut child);
+
+    // The parent is underneath the child
+    for events in click(button.rect.center()) {
+        let button = stacked_frame(&ctx, events, &mut parent, &mut child).unwrap();
+        assert!(!button.hovered());
+        assert!(!button.clicked());
+        if child.is_open() {
+            continue;
+        }
+        // Closed by the click outside of it
+        assert!(parent.is_open());
+    }
+    assert!(!child.is_open());
+
+    child.open();
+    stacked_frame(&ctx, vec![], &mut parent, &mut child);
+    stacked_frame(&ctx, key_press(Key::Escape), &mut parent, &mut child);
+    assert!(!child.is_open());
+    assert!(parent.is_open());
+
+    stacked_frame(&ctx, key_press(Key::Escape), &mut parent, &mut child);
+    assert!(!parent.is_open());
+}
+
+#[test]
+fn modals_with_the_same_title_are_told_apart_by_their_id() {
+    let ctx = context();
+    let mut parent = ModalHandler::default();
+    let mut child = ModalHandler::default();
+    let run = |events, parent: &mut ModalHandler, child: &mut ModalHandler| {
+        let _ = ctx.run(input(events), |ctx| {
+            let modal = |id| move || Modal::new("Delete").id(egui::Id::new(id));
+            parent.ui(ctx, modal("parent"), |ui, _| {
+                child.ui(ui.ctx(), modal("child"), |ui, _| ui.label("Child"));
+            });
+        });
+    };
+    assert!(!Modal::is_any_open(&ctx));
+    parent.open();
+    child.open();
+    run(vec![], &mut parent, &mut child);
+    run(vec![], &mut parent, &mut child);
+    assert!(Modal::is_any_open(&ctx));
+
+    // Each modal has its own place in the stack
+    run(key_press(Key::Escape), &mut parent, &mut child);
+    assert!(!child.is_open());
+    assert!(parent.is_open());
+
+    run(key_press(Key::Escape), &mut parent, &mut child);
+    assert!(!parent.is_open());
+    run(vec![], &mut parent, &mut child);
+    assert!(!Modal::is_any_open(&ctx));
+}
+
+#[test]
+fn dismissed_dialog_returns_its_result_once() {
+    let ctx = context();
+    let mut handler = ModalHandler::default();
+    handler.open();
+
+    let mut dialog_frame = |events| {
+        let mut result = None;
+        let _ = ctx.run(input(events), |ctx| {
+            ConfirmDialog::new("Confirm", "Sure?").show(ctx, &mut handler);
+            result = handler.take_result();
+        });
+        result
+    };
+    assert_eq!(dialog_frame(vec![]), None);
+    assert_eq!(
+        dialog_frame(key_press(Key::Escape)),
+        Some(DialogResult::Dismissed)
+    );
+    assert_eq!(dialog_frame(vec![]), None);
+}
+
+#[test]
+fn enter_triggers_the_primary_action() {
+    let ctx = context();
+    let mut handler = ModalHandler::default();
+    handler.open();
+
+    let mut confirm_frame = |events| {
+        let mut result = None;
+        let _ = ctx.run(input(events), |ctx| {
+            ConfirmDialog::new("Confirm", "Sure?").show(ctx, &mut handler);
+            result = handler.take_result();
+        });
+        result
+    };
+    assert_eq!(confirm_frame(vec![]), None);
+    assert_eq!(
+        confirm_frame(key_press(Key::Enter)),
+        Some(DialogResult::Primary(()))
+    );
+}
+
+#[derive(Default)]
+struct Form {
+    remember: bool,
+    name: String,
+    notes: String,
+}
+
+/// Runs a frame with a checkbox, a single-line and a multiline text input above a footer, and
+/// returns their ids and the footer action.
+fn form_frame(
+    ctx: &Context,
+    events: Vec<Event>,
+    modal: &mut Modal,
+    form: &mut Form,
+) -> ([egui::Id; 3], Option<FooterAction>) {
+    let mut ids = [egui::Id::NULL; 3];
+    let mut action = None;
+    let _ = ctx.run(input(events), |ctx| {
+        modal.ui(ctx, |ui, _| {
+            ids[0] = ui.checkbox(&mut form.remember, "Remember").id;
+            ids[1] = ui.text_edit_singleline(&mut form.name).id;
+            ids[2] = ui.text_edit_multiline(&mut form.notes).id;
+            action = ModalFooter::new("Save").secondary("Cancel").show(ui);
+        });
+    });
+    (ids, action)
+}
+
+#[test]
+fn enter_is_left_to_the_focused_widget_when_it_reacts_to_it() {
+    let ctx = context();
+    let mut modal = Modal::new("Form");
+    let mut form = Form::default();
+    form_frame(&ctx, vec![], &mut modal, &mut form);
+    let ([checkbox, single_line, multiline], _) = form_frame(&ctx, vec![], &mut modal, &mut form);
+
+    let mut enter_on = |id| {
+        ctx.memory_mut(|mem| mem.request_focus(id));
+        form_frame(&ctx, vec![], &mut modal, &mut form);
+        form_frame(&ctx, key_press(Key::Enter), &mut modal, &mut form).1
+    };
+
+    // Clicks the checkbox
+    assert_eq!(enter_on(checkbox), None);
+    // Ends the input
+    assert_eq!(enter_on(single_line), Some(FooterAction::Primary));
+    // Inserts a new line
+    assert_eq!(enter_on(multiline), None);
+
+    assert!(form.remember);
+    assert_eq!(form.name, "");
+    assert_eq!(form.notes, "\n");
+}
+
+#[test]
+fn enter_is_ignored_while_the_prompt_is_invalid() {
+    let ctx = context();
+    let mut handler = ModalHandler::default();
+
+    let prompt_frame = |handler: &mut ModalHandler<(), _>, events, initial_text: &str| {
+        let mut result = None;
+        let _ = ctx.run(input(events), |ctx| {
+            PromptDialog::new("Name", "Name:")
+                .initial_text(initial_text)
+                .validate(|name| {
+                    if name.is_empty() {
+                        Err("Empty".to_owned())
+                    } else {
+                        Ok(())
+                    }
+                })
+                .show(ctx, handler);
+            result = handler.take_result();
+        });
+        result
+    };
+
+    handler.open();
+    assert_eq!(prompt_frame(&mut handler, vec![], ""), None);
+    assert_eq!(prompt_frame(&mut handler, key_press(Key::Enter), ""), None);
+    assert_eq!(
+        prompt_frame(&mut handler, key_press(Key::Escape), ""),
+        Some(DialogResult::Dismissed)
+    );
+
+    handler.open();
+    assert_eq!(prompt_frame(&mut handler, vec![], "Ferris"), None);
+    assert_eq!(
+        prompt_frame(&mut handler, key_press(Key::Enter), "Ferris"),
+        Some(DialogResult::Primary("Ferris".to_owned()))
+    );
+}
+
+#[test]
+fn prompts_with_the_same_title_keep_their_own_text() {
+    let ctx = context();
+    let mut first = ModalHandler::default();
+    let mut second = ModalHandler::default();
+    first.open();
+    second.open();
+
+    let mut prompts_frame = |events| {
+        let mut results = (None, None);
+        let _ = ctx.run(input(events), |ctx| {
+            let prompt = |id| PromptDialog::new("Rename", "Name:").id(egui::Id::new(id));
+            prompt("first").initial_text("first").show(ctx, &mut first);
+            prompt("second")
+                .initial_text("second")
+                .show(ctx, &mut second);
+            results = (first.take_result(), second.take_result());
+        });
+        results
+    };
+    assert_eq!(prompts_frame(vec![]), (None, None));
+    assert_eq!(
+        prompts_frame(key_press(Key::Enter)),
+        (None, Some(DialogResult::Primary("second".to_owned())))
+    );
+    assert_eq!(
+        prompts_frame(key_press(Key::Enter)),
+        (Some(DialogResult::Primary("first".to_owned())), None)
+    );
+}
+
+#[test]
+fn handler_keeps_the_modal_during_the_close_animation() {
+    let ctx = context();
+
+    for (animation, animated) in [
+        (ModalAnimation::DEFAULT, true),
+        (ModalAnimation::NONE, false),
+    ] {
+        let mut handler = ModalHandler::default();
+        let handler_frame = |handler: &mut ModalHandler, events| {
+            let _ = ctx.run(input(events), |ctx| {
+                handler.ui(
+                    ctx,
+                    || Modal::new("Animated").animation(animation),
+                    |ui, _| ui.label("Content"),
+                );
+            });
+        };
+
+        handler.open();
+        for _ in 0..30 {
+            handler_frame(&mut handler, vec![]);
+        }
+        handler_frame(&mut handler, key_press(Key::Escape));
+        assert!(!handler.is_open());
+
+        let mut frames = 0;
+        while handler.is_visible() {
+            handler_frame(&mut handler, vec![]);
+            frames += 1;
+            assert!(frames < 100, "the close animation never ends");
+        }
+        assert_eq!(frames > 0, animated, "{animation:?}");
+    }
+}
+
+/// The rect of the modal window after a few frames, once it has settled.
+fn modal_rect(ctx: &Context, modal: &mut Modal) -> Rect {
+    for _ in 0..3 {
+        let _ = ctx.run(input(vec![]), |ctx| {
+            modal.ui(ctx, |ui, _| ui.label("Content"));
+        });
+    }
+    ctx.memory(|mem| mem.area_rect(egui::Id::new("Positioned")))
+        .unwrap()
+}
+
+#[test]
+fn positions() {
+    let ctx = context();
+    let screen = screen();
+    let positioned = || Modal::new("Positioned").animation(ModalAnimation::NONE);
+
+    let rect = modal_rect(&ctx, &mut positioned());
+    assert_eq!(rect.center_top().x, screen.center().x);
+    assert_eq!(rect.top(), 60.0);
+
+    let rect = modal_rect(&ctx, &mut positioned().position(ModalPosition::Center));
+    assert!((rect.center() - screen.center()).length() < 1.0, "{rect:?}");
+
+    let anchor = Rect::from_min_size(Pos2::new(100.0, 500.0), egui::vec2(80.0, 20.0));
+    let rect = modal_rect(
+        &ctx,
+        &mut positioned().position(ModalPosition::Anchored(anchor)),
+    );
+    assert_eq!(rect.left(), anchor.left());
+    assert!(
+        rect.bottom() < anchor.top(),
+        "{rect:?} opens above the anchor"
+    );
+
+    let rect = modal_rect(&ctx, &mut positioned().position(ModalPosition::BottomSheet));
+    assert_eq!(rect.bottom(), screen.bottom());
+    assert_eq!(rect.width(), screen.width());
+
+    let rect = modal_rect(&ctx, &mut positioned().max_width(200.0));
+    assert!(rect.width() <= 200.0, "{rect:?}");
+
+    let rect = modal_rect(&ctx, &mut positioned().full_screen_below_width(1000.0));
+    assert_eq!(rect, screen);
+}
+
+fn shift_tab() -> Vec<Event> {
+    vec![Event::Key {
+        key: Key::Tab,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::SHIFT,
+    }]
+}
+
+/// Runs a frame with a button in the background and, if `handler` is open, a modal with the
+/// buttons `A` and `B`. Returns the ids of the three buttons.
+fn focus_frame(
+    ctx: &Context,
+    events: Vec<Event>,
+    handler: &mut ModalHandler,
+    initial_focus: Option<egui::Id>,
+) -> [egui::Id; 3] {
+    let mut ids = [egui::Id::NULL; 3];
+    let _ = ctx.run(input(events), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ids[0] = ui.button("Behind").id;
+        });
+        let make_modal = || match initial_focus {
+            Some(id) => Modal::new("Focus").initial_focus(id),
+            None => Modal::new("Focus"),
+        };
+        handler.ui(ctx, make_modal, |ui, _| {
+            ids[1] = ui.button("A").id;
+            ids[2] = ui.button("B").id;
+        });
+    });
+    ids
+}
+
+fn focused(ctx: &Context) -> Option<egui::Id> {
+    ctx.memory(|mem| mem.focused())
+}
+
+#[test]
+fn focus_moves_into_the_modal_and_back() {
+    let ctx = context();
+    let mut handler = ModalHandler::default();
+
+    let [behind, ..] = focus_frame(&ctx, vec![], &mut handler, None);
+    ctx.memory_mut(|mem| mem.request_focus(behind));
+    focus_frame(&ctx, vec![], &mut handler, None);
+    assert_eq!(focused(&ctx), Some(behind));
+
+    // The first widget is only known once Tab moved to it
+    handler.open();
+    focus_frame(&ctx, vec![], &mut handler, None);
+    let [_, a, b] = focus_frame(&ctx, vec![], &mut handler, None);
+    assert_ne!(focused(&ctx), Some(behind));
+    focus_frame(&ctx, key_press(Key::Tab), &mut handler, None);
+    assert_eq!(focused(&ctx), Some(a));
+    focus_frame(&ctx, key_press(Key::Escape), &mut handler, None);
+    assert_eq!(focused(&ctx), Some(behind));
+
+    // Then focused when the modal opens again
+    handler.open();
+    focus_frame(&ctx, vec![], &mut handler, None);
+    assert_eq!(focused(&ctx), Some(a));
+    focus_frame(&ctx, key_press(Key::Escape), &mut handler, None);
+    assert_eq!(focused(&ctx), Some(behind));
+
+    handler.open();
+    focus_frame(&ctx, vec![], &mut handler, Some(b));
+    assert_eq!(focused(&ctx), Some(b));
+
+    focus_frame(&ctx, key_press(Key::Escape), &mut handler, None);
+    assert_eq!(focused(&ctx), Some(behind));
+}
+
+#[test]
+fn tab_cycles_within_the_modal() {
+    let ctx = context();
+    let mut handler = ModalHandler::default();
+    handler.open();
+
+    // The window is invisible in its first frame, to measure its content
+    focus_frame(&ctx, vec![], &mut handler, None);
+    let [behind, a, b] = focus_frame(&ctx, vec![], &mut handler, None);
+    // Focus changes with Shift+Tab take a frame
+    let mut focus_after = |events| {
+        focus_frame(&ctx, events, &mut handler, None);
+        focus_frame(&ctx, vec![], &mut handler, None);
+        focused(&ctx)
+    };
+
+    let forwards = (0..5)
+        .map(|_| focus_after(key_press(Key::Tab)))
+        .collect::<Vec<_>>();
+    assert_eq!(forwards, [a, b, a, b, a].map(Some));
+
+    let backwards = (0..4).map(|_| focus_after(shift_tab())).collect::<Vec<_>>();
+    assert_eq!(backwards, [b, a, b, a].map(Some));
+    assert!(!backwards.contains(&Some(behind)));
+
+    // The first widget is known from now on, and focused when the modal opens again
+    focus_frame(&ctx, key_press(Key::Escape), &mut handler, None);
+    handler.open();
+    focus_frame(&ctx, vec![], &mut handler, None);
+    assert_eq!(focused(&ctx), Some(a));
+}
+
+#[test]
+fn typed_handler_passes_its_payload_and_returns_a_result() {
+    let ctx = context();
+    let mut handler = ModalHandler::<u32, String>::default();
+
+    let typed_frame = |handler: &mut ModalHandler<u32, String>, events| {
+        let mut payload = None;
+        let _ = ctx.run(input(events), |ctx| {
+            handler.show(
+                ctx,
+                |item| Modal::new(&format!("Delete item {item}")),
+                |_, item| {
+                    payload = Some(*item);
+                    (*item == 2).then(|| format!("deleted {item}"))
+                },
+            );
+        });
+        payload
+    };
+
+    assert_eq!(typed_frame(&mut handler, vec![]), None);
+
+    handler.open_with(1);
+    assert_eq!(typed_frame(&mut handler, vec![]), Some(1));
+    assert_eq!(handler.take_result(), None);
+    typed_frame(&mut handler, key_press(Key::Escape));
+    assert!(!handler.is_open());
+    assert_eq!(handler.take_result(), None);
+
+    handler.open_with(2);
+    assert_eq!(typed_frame(&mut handler, vec![]), Some(2));
+    assert!(!handler.is_open());
+    assert_eq!(handler.take_result(), Some("deleted 2".to_owned()));
+    assert_eq!(handler.take_result(), None);
+}
+
+/// Sets its flag when woken.
+struct FlagWaker(AtomicBool);
+
+impl std::task::Wake for FlagWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn async_requests_resolve_in_order() {
+    use std::future::Future;
+    use std::task::{Context as TaskContext, Poll};
+
+    let ctx = context();
+    let mut handler = AsyncModalHandler::<u32, String>::default();
+    let requester = handler.requester();
+
+    let async_frame = |handler: &mut AsyncModalHandler<u32, String>, events| {
+        let mut payload = None;
+        let _ = ctx.run(input(events), |ctx| {
+            handler.show(
+                ctx,
+                |item| Modal::new(&format!("Delete item {item}")),
+                |_, item| {
+                    payload = Some(*item);
+                    (*item == 3).then(|| format!("deleted {item}"))
+                },
+            );
+        });
+        payload
+    };
+
+    let mut first = Box::pin(requester.request(1));
+    let cancelled = requester.request(2);
+    let mut third = requester.clone().request(3);
+    drop(cancelled);
+    assert!(handler.is_open());
+
+    let waker = Arc::new(FlagWaker(AtomicBool::new(false)));
+    let woken = waker.clone();
+    let waker = waker.into();
+    let mut task_cx = TaskContext::from_waker(&waker);
+    assert_eq!(first.as_mut().poll(&mut task_cx), Poll::Pending);
+
+    assert_eq!(async_frame(&mut handler, vec![]), Some(1));
+    async_frame(&mut handler, key_press(Key::Escape));
+    assert!(woken.0.load(Ordering::SeqCst));
+    assert_eq!(first.as_mut().poll(&mut task_cx), Poll::Ready(None));
+
+    // The second request was cancelled when its future was dropped
+    assert_eq!(third.try_take(), None);
+    assert_eq!(async_frame(&mut handler, vec![]), Some(3));
+    assert_eq!(third.try_take(), Some(Some("deleted 3".to_owned())));
+    assert!(!handler.is_open());
+}
+
+#[test]
+fn async_requests_resolve_to_none_once_the_handler_is_dropped() {
+    let ctx = context();
+    let mut handler = AsyncModalHandler::<u32, String>::default();
+    let requester = handler.requester();
+
+    let mut shown = requester.request(1);
+    let mut queued = requester.request(2);
+    let _ = ctx.run(input(vec![]), |ctx| {
+        handler.show(ctx, |_| Modal::new("Delete item"), |_, _| None);
+    });
+    assert_eq!(shown.try_take(), None);
+
+    drop(handler);
+    assert_eq!(shown.try_take(), Some(None));
+    assert_eq!(queued.try_take(), Some(None));
+    assert_eq!(requester.request(3).try_take(), Some(None));
+}
@@ -0,0 +1,254 @@
+//! Property checks of the number formatter on pseudo-random values: it must round-trip, and
+//! behave like the `re_format` based formatter it replaces.
+
+use std::ops::RangeInclusive;
+
+use egui::NumExt;
+
+use egui_ui_refresh::number_format::NumberFormat;
+
+const SAMPLES: usize = 20_000;
+
+/// Deterministic pseudo-random numbers (splitmix64), so that failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A value of any magnitude below `max_exponent`, sometimes with few decimals as if typed
+    /// by a user or set by a slider step.
+    fn value(&mut self, max_exponent: i32) -> f64 {
+        let mantissa = (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+        let exponent = self.below((max_exponent + 13) as usize) as i32 - 12;
+        let mut value = mantissa * 10_f64.powi(exponent);
+        match self.below(4) {
+            0 => value = value.round(),
+            1 => {
+                let scale = 10_f64.powi(self.below(4) as i32);
+                value = (value * scale).round() / scale;
+            }
+            _ => {}
+        }
+        // Negative zero is covered by `special_values_match_reference`
+        if self.below(2) == 0 && value != 0.0 {
+            -value
+        } else {
+            value
+        }
+    }
+
+    fn decimal_range(&mut self) -> RangeInclusive<usize> {
+        let min = self.below(8);
+        let max = min + self.below(10);
+        min..=max
+    }
+}
+
+/// The formatter of the theme before `re_format` was dropped.
+fn reference_format_with_decimals_in_range(
+    value: f64,
+    decimal_range: RangeInclusive<usize>,
+) -> String {
+    fn format_with_decimals(value: f64, decimals: usize) -> String {
+        re_format::FloatFormatOptions::DEFAULT_f64
+            .with_decimals(decimals)
+            .with_strip_trailing_zeros(false)
+            .format(value)
+    }
+
+    let epsilon = 16.0 * f32::EPSILON;
+
+    let min_decimals = *decimal_range.start();
+    let max_decimals = *decimal_range.end();
+    let max_decimals = max_decimals.at_most(16);
+    let min_decimals = min_decimals.at_most(max_decimals);
+
+    if min_decimals < max_decimals {
+        for decimals in min_decimals..max_decimals {
+            let text = format_with_decimals(value, decimals);
+            if let Some(parsed) = re_format::parse_f64(&text) {
+                if egui::emath::almost_equal(parsed as f32, value as f32, epsilon) {
+                    return text;
+                }
+            }
+        }
+    }
+
+    format_with_decimals(value, max_decimals)
+}
+
+#[test]
+fn format_matches_reference() {
+    let mut rng = Rng(1);
+    for _ in 0..SAMPLES {
+        // Above this the default format switches to scientific notation on its own terms
+        let value = rng.value(14);
+        let decimal_range = rng.decimal_range();
+        assert_eq!(
+            NumberFormat::DEFAULT.format_with_decimals_in_range(value, decimal_range.clone()),
+            reference_format_with_decimals_in_range(value, decimal_range.clone()),
+            "formatting {value:?} with {decimal_range:?} decimals",
+        );
+    }
+}
+
+#[test]
+fn format_of_large_values_matches_reference() {
+    // Without grouping nor scientific thresholds, the formatter falls back to the reference
+    // notation for values with more digits than `f64` can hold.
+    let plain = NumberFormat::DEFAULT
+        .with_separators(None, '.')
+        .with_scientific_thresholds(0.0, f64::INFINITY);
+
+    let mut rng = Rng(2);
+    for _ in 0..SAMPLES {
+        let value = rng.value(30);
+        let decimals = rng.below(17);
+        let reference = re_format::FloatFormatOptions::DEFAULT_f64
+            .with_decimals(decimals)
+            .with_strip_trailing_zeros(false)
+            .format(value)
+            .replace('\u{2009}', "");
+        assert_eq!(
+            plain.format(value, decimals),
+            reference,
+            "formatting {value:?} with {decimals} decimals",
+        );
+    }
+}
+
+#[test]
+fn special_values_match_reference() {
+    for value in [0.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+        for decimals in 0..=3 {
+            assert_eq!(
+                NumberFormat::DEFAULT.format_with_decimals_in_range(value, 0..=decimals),
+                reference_format_with_decimals_in_range(value, 0..=decimals),
+            );
+        }
+    }
+
+    // The reference wrote negative zero with a hyphen, which is not kept
+    assert_eq!(reference_format_with_decimals_in_range(-0.0, 0..=2), "-0");
+    assert_eq!(NumberFormat::DEFAULT.format_with_decimals_in_range(-0.0, 0..=2), "0");
+}
+
+#[test]
+fn parse_matches_reference() {
+    let mut texts: Vec<String> = [
+        "",
+        " ",
+        "-",
+        "−",
+        ".",
+        "1.",
+        ".5",
+        "1e5",
+        "1E-5",
+        "−1e5",
+        "+3",
+        "− 3",
+        "1 000",
+        "1\u{2009}000.25",
+        "inf",
+        "-inf",
+        "NaN",
+        "∞",
+        "0x10",
+        "1,5",
+        "1.2.3",
+        "12k",
+        "abc",
+    ]
+    .map(str::to_owned)
+    .to_vec();
+
+    let mut rng = Rng(3);
+    for _ in 0..SAMPLES {
+        let value = rng.value(20);
+        texts.push(NumberFormat::DEFAULT.format(value, rng.below(17)));
+    }
+
+    for text in &texts {
+        let parsed = NumberFormat::DEFAULT.parse(text);
+        let reference = re_format::parse_f64(text);
+        assert_eq!(
+            parsed.map(f64::to_bits),
+            reference.map(f64::to_bits),
+            "parsing {text:?}",
+        );
+    }
+}
+
+#[test]
+fn si_suffixes_are_picked_after_rounding() {
+    let si = NumberFormat::DEFAULT
+        .with_si_suffixes(true)
+        .with_separators(None, '.');
+    assert_eq!(si.format(999.94, 1), "999.9");
+    assert_eq!(si.format(999.96, 1), "1.0k");
+    assert_eq!(si.format(999_950.0, 1), "1.0M");
+    assert_eq!(si.format(-999_950.0, 1), "−1.0M");
+    assert_eq!(si.format_with_decimals_in_range(999_999.0, 0..=3), "1M");
+
+    // Past the largest suffix
+    assert_eq!(si.format(999.94e18, 1), "999.9E");
+    assert_eq!(si.format(999.96e18, 1), "1.0e21");
+    assert_eq!(si.format(1e21, 1), "1.0e21");
+    assert_eq!(si.format(1e28, 0), "1e28");
+
+    let mut rng = Rng(5);
+    for _ in 0..SAMPLES {
+        let value = rng.value(25);
+        let decimals = rng.below(4);
+        let text = si.format(value, decimals);
+        if let Some(scaled) = text.strip_suffix(['k', 'M', 'G', 'T', 'P', 'E']) {
+            let scaled = si.parse(scaled).unwrap().abs();
+            assert!(
+                (1.0..1000.0).contains(&scaled),
+                "formatted {value:?} with {decimals} decimals as {text:?}",
+            );
+        }
+    }
+}
+
+#[test]
+fn round_trip() {
+    let formats = [
+        NumberFormat::DEFAULT,
+        NumberFormat::DEFAULT.with_separators(Some(','), '.'),
+        NumberFormat::DEFAULT.with_separators(Some('.'), ','),
+        NumberFormat::DEFAULT.with_separators(Some('\u{202F}'), ','),
+        NumberFormat::DEFAULT.with_separators(None, '.'),
+        NumberFormat::DEFAULT.with_unicode_minus(false),
+        NumberFormat::DEFAULT.with_scientific_thresholds(1e-3, 1e6),
+        NumberFormat::DEFAULT.with_si_suffixes(true),
+    ];
+
+    let mut rng = Rng(4);
+    for _ in 0..SAMPLES {
+        let value = rng.value(20);
+        // With enough decimals allowed, the value is shown accurately
+        let decimal_range = rng.below(8)..=16;
+        for format in &formats {
+            let text = format.format_with_decimals_in_range(value, decimal_range.clone());
+            let parsed = format
+                .parse(&text)
+                .unwrap_or_else(|| panic!("{format:?} can't parse {text:?}"));
+            assert!(
+                egui::emath::almost_equal(parsed as f32, value as f32, 16.0 * f32::EPSILON),
+                "{format:?} formatted {value:?} as {text:?}, parsed back as {parsed:?}",
+            );
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Applies the theme to egui contexts with and without the bundled fonts.
+
+use egui::{CentralPanel, Context, FontFamily, RawInput, TextStyle};
+
+use egui_ui_refresh::typography::Typography;
+use egui_ui_refresh::RefreshedTheme;
+
+/// Runs a frame using the heading and every style of the type scale.
+fn run_frame(ctx: &Context) {
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Heading");
+            for typography in Typography::ALL {
+                ui.label(typography.rich_text(typography.name()));
+            }
+        });
+    });
+}
+
+fn family(ctx: &Context, text_style: &TextStyle) -> FontFamily {
+    ctx.style().text_styles[text_style].family.clone()
+}
+
+#[test]
+fn apply_on_a_plain_context_uses_the_egui_families() {
+    let theme = RefreshedTheme::init_default();
+
+    let ctx = Context::default();
+    theme.apply(&ctx);
+    run_frame(&ctx);
+    assert_eq!(family(&ctx, &TextStyle::Heading), FontFamily::Proportional);
+    assert_eq!(
+        family(&ctx, &Typography::Title.text_style()),
+        FontFamily::Proportional
+    );
+    assert_eq!(
+        family(&ctx, &Typography::Code.text_style()),
+        FontFamily::Monospace
+    );
+
+    // Once the fonts are loaded, the families they define are known
+    theme.apply(&ctx);
+    run_frame(&ctx);
+    assert_eq!(family(&ctx, &TextStyle::Heading), FontFamily::Proportional);
+}
+
+#[cfg(feature = "fonts")]
+#[test]
+fn apply_with_fonts_uses_their_families() {
+    use egui_ui_refresh::fonts::{self, font_family_medium, font_family_semibold};
+
+    let theme = RefreshedTheme::init_default();
+
+    let ctx = Context::default();
+    theme.apply_with_fonts(&ctx, fonts::fonts());
+    run_frame(&ctx);
+    assert_eq!(family(&ctx, &TextStyle::Heading), font_family_semibold());
+    assert_eq!(
+        family(&ctx, &Typography::Title.text_style()),
+        font_family_semibold()
+    );
+    assert_eq!(
+        family(&ctx, &Typography::Overline.text_style()),
+        font_family_medium()
+    );
+
+    // The families of the fonts loaded by a previous frame
+    let ctx = Context::default();
+    ctx.set_fonts(fonts::fonts());
+    let _ = ctx.run(RawInput::default(), |_| {});
+    theme.apply(&ctx);
+    run_frame(&ctx);
+    assert_eq!(family(&ctx, &TextStyle::Heading), font_family_semibold());
+}
@@ -0,0 +1,118 @@
+//! Relative time buckets, their refresh intervals, and the rounding of durations at the edges of
+//! their units.
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use egui_ui_refresh::time_format::TimeFormat;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+#[test]
+fn relative_time_buckets() {
+    let format = TimeFormat::new();
+    let now = UNIX_EPOCH + secs(1_714_566_896);
+
+    let cases = [
+        (1, "just now", "in a moment"),
+        (59, "just now", "in a moment"),
+        (MINUTE, "1 min ago", "in 1 min"),
+        (HOUR - 1, "59 min ago", "in 59 min"),
+        (HOUR, "1 h ago", "in 1 h"),
+        (DAY - 1, "23 h ago", "in 23 h"),
+        (DAY, "1 d ago", "in 1 d"),
+        (30 * DAY - 1, "29 d ago", "in 29 d"),
+    ];
+    for (elapsed, past, future) in cases {
+        assert_eq!(format.format_relative(now - secs(elapsed), now), past);
+        assert_eq!(format.format_relative(now + secs(elapsed), now), future);
+    }
+
+    // Past a month, the date
+    assert_eq!(
+        format.format_relative(now - secs(30 * DAY), now),
+        "2024-04-01"
+    );
+    assert_eq!(
+        format.format_relative(now + secs(30 * DAY), now),
+        "2024-05-31"
+    );
+}
+
+#[test]
+fn relative_time_refreshes_when_the_text_changes() {
+    let format = TimeFormat::new();
+    let now = UNIX_EPOCH + secs(1_714_566_896);
+
+    let past = [
+        (0, secs(MINUTE)),
+        (30, secs(30)),
+        (MINUTE, secs(MINUTE)),
+        (HOUR - 1, secs(1)),
+        (HOUR, secs(HOUR)),
+        (DAY + 1, secs(DAY - 1)),
+    ];
+    for (elapsed, interval) in past {
+        let time = now - secs(elapsed);
+        assert_eq!(
+            format.refresh_interval(time, now),
+            interval,
+            "{elapsed} s ago"
+        );
+        let before = format.format_relative(time, now + interval - Duration::from_millis(1));
+        assert_eq!(before, format.format_relative(time, now), "{elapsed} s ago");
+    }
+
+    // When the remaining time is a multiple of the step, the text changes right away
+    let future = [
+        (30, secs(30)),
+        (MINUTE, Duration::from_nanos(1)),
+        (MINUTE + 20, secs(20)),
+        (HOUR, Duration::from_nanos(1)),
+        (DAY + HOUR, secs(HOUR)),
+    ];
+    for (remaining, interval) in future {
+        let time = now + secs(remaining);
+        assert_eq!(
+            format.refresh_interval(time, now),
+            interval,
+            "in {remaining} s"
+        );
+        assert_ne!(
+            format.format_relative(time, now + interval + Duration::from_millis(1)),
+            format.format_relative(time, now),
+            "in {remaining} s"
+        );
+    }
+}
+
+#[test]
+fn durations_round_to_their_unit() {
+    let format = TimeFormat::new();
+    let cases = [
+        (Duration::ZERO, "0 ns"),
+        (Duration::from_nanos(999), "999 ns"),
+        (Duration::from_nanos(1_000), "1 µs"),
+        (Duration::from_nanos(9_996), "10 µs"),
+        (Duration::from_nanos(99_960), "100 µs"),
+        (Duration::from_nanos(999_600), "1 ms"),
+        (Duration::from_micros(1500), "1.5 ms"),
+        (Duration::from_micros(999_999), "1 s"),
+        (Duration::from_millis(4_250), "4.25 s"),
+        (Duration::from_millis(59_940), "59.9 s"),
+        (Duration::from_millis(59_960), "1 min"),
+        (secs(MINUTE), "1 min"),
+        (secs(200), "3 min 20 s"),
+        (secs(HOUR - 1), "59 min 59 s"),
+        (secs(2 * HOUR + 5 * MINUTE), "2 h 5 min"),
+        (secs(3 * DAY + 4 * HOUR), "3 d 4 h"),
+    ];
+    for (duration, expected) in cases {
+        assert_eq!(format.format_duration(duration), expected, "{duration:?}");
+    }
+}
@@ -0,0 +1,70 @@
+//! Choice of the scale of values with a unit, around the boundaries between scales.
+
+use egui_ui_refresh::units::{Unit, UnitFormat};
+
+const KIB: f64 = 1024.0;
+const MIB: f64 = 1024.0 * 1024.0;
+
+#[test]
+fn bytes_move_up_when_rounded_to_the_next_scale() {
+    let bytes = UnitFormat::new(Unit::Bytes);
+    assert_eq!(bytes.format(1023.4, 0..=0), "1\u{2009}023 B");
+    assert_eq!(bytes.format(1023.9, 0..=0), "1 KiB");
+    assert_eq!(bytes.format(-1023.9, 0..=0), "−1 KiB");
+    assert_eq!(bytes.format(MIB - 1.0, 0..=0), "1 MiB");
+    assert_eq!(bytes.format(MIB - 0.5 * KIB, 0..=0), "1\u{2009}023.5 KiB");
+}
+
+#[test]
+fn seconds_move_up_when_rounded_to_the_next_scale() {
+    let seconds = UnitFormat::new(Unit::Seconds);
+    assert_eq!(seconds.format(0.000_999_9, 0..=3), "1 ms");
+    assert_eq!(seconds.format(0.000_999_9, 0..=7), "999.9 µs");
+    assert_eq!(seconds.format(59.99, 0..=1), "1 min");
+    assert_eq!(seconds.format(3599.9, 0..=0), "1 h");
+    assert_eq!(seconds.format(3599.9, 0..=2), "59.9983 min");
+}
+
+/// The scales of each unit below the largest, with the factor to the next scale.
+const NEXT_SCALES: [(Unit, &[(&str, f64)]); 2] = [
+    (
+        Unit::Bytes,
+        &[("B", 1024.0), ("KiB", 1024.0), ("MiB", 1024.0)],
+    ),
+    (
+        Unit::Seconds,
+        &[
+            ("ns", 1000.0),
+            ("µs", 1000.0),
+            ("ms", 1000.0),
+            ("s", 60.0),
+            ("min", 60.0),
+        ],
+    ),
+];
+
+#[test]
+fn numbers_stay_below_the_next_scale() {
+    // Deterministic values spread over every scale
+    let values = (0..20_000).map(|i| {
+        let mantissa = (i as f64 * 0.618_033_988_749_895).fract();
+        mantissa * 10_f64.powi(i % 30 - 12)
+    });
+
+    for value in values {
+        for (unit, next_scales) in NEXT_SCALES {
+            let format = UnitFormat::new(unit);
+            for decimals in 0..=4 {
+                let text = format.format(value, 0..=decimals);
+                let (number, suffix) = text.rsplit_once(' ').unwrap();
+                if let Some((_, next)) = next_scales.iter().find(|(scale, _)| *scale == suffix) {
+                    let number = format.number_format.parse(number).unwrap();
+                    assert!(
+                        number < *next,
+                        "formatted {value:?} with {decimals} decimals as {text:?}",
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,420 @@
+#!/usr/bin/env python3
+"""Generate Inter SemiBold, Bold and Bold Italic from the bundled Inter Medium and Italic.
+
+The outlines are emboldened like FreeType's `FT_Outline_EmboldenXY`: every point moves along the
+bisector of its edges, by the difference between the stems of Inter Regular and Medium for each
+weight step. The baseline and the cap height are kept, the advances grow with the stems, and the
+hinting instructions are dropped.
+
+Usage, from the root of the repository: python3 tools/embolden_inter.py
+"""
+
+import math
+import struct
+
+FONTS_DIR = "src/inter/"
+
+# Simple glyph flags
+ON_CURVE = 0x01
+X_SHORT = 0x02
+Y_SHORT = 0x04
+REPEAT = 0x08
+X_SAME_OR_POSITIVE = 0x10
+Y_SAME_OR_POSITIVE = 0x20
+OVERLAP_SIMPLE = 0x40
+
+# Composite glyph flags
+ARGS_ARE_WORDS = 0x0001
+ARGS_ARE_XY_VALUES = 0x0002
+HAVE_SCALE = 0x0008
+MORE_COMPONENTS = 0x0020
+HAVE_X_AND_Y_SCALE = 0x0040
+HAVE_TWO_BY_TWO = 0x0080
+HAVE_INSTRUCTIONS = 0x0100
+
+
+def read_tables(path):
+    data = open(path, "rb").read()
+    (count,) = struct.unpack(">H", data[4:6])
+    tables = {}
+    for i in range(count):
+        tag, _, offset, length = struct.unpack(">4sIII", data[12 + 16 * i : 28 + 16 * i])
+        tables[tag.decode()] = data[offset : offset + length]
+    return tables
+
+
+def read_glyphs(tables):
+    (count,) = struct.unpack(">H", tables["maxp"][4:6])
+    (long_offsets,) = struct.unpack(">h", tables["head"][50:52])
+    if long_offsets:
+        offsets = struct.unpack(f">{count + 1}I", tables["loca"][: 4 * (count + 1)])
+    else:
+        offsets = [2 * o for o in struct.unpack(f">{count + 1}H", tables["loca"][: 2 * (count + 1)])]
+    glyf = tables["glyf"]
+    return [glyf[offsets[i] : offsets[i + 1]] for i in range(count)]
+
+
+def parse_glyph(data):
+    if not data:
+        return None
+    (contours,) = struct.unpack(">h", data[:2])
+    if contours < 0:
+        return parse_composite(data)
+
+    ends = list(struct.unpack(f">{contours}H", data[10 : 10 + 2 * contours]))
+    pos = 10 + 2 * contours
+    (instructions,) = struct.unpack(">H", data[pos : pos + 2])
+    pos += 2 + instructions
+
+    points = ends[-1] + 1 if contours else 0
+    flags = []
+    while len(flags) < points:
+        flag = data[pos]
+        pos += 1
+        flags.append(flag)
+        if flag & REPEAT:
+            flags.extend([flag] * data[pos])
+            pos += 1
+
+    def coordinates(short, same_or_positive):
+        nonlocal pos
+        values = []
+        value = 0
+        for flag in flags:
+            if flag & short:
+                delta = data[pos]
+                pos += 1
+                value += delta if flag & same_or_positive else -delta
+            elif not flag & same_or_positive:
+                value += struct.unpack(">h", data[pos : pos + 2])[0]
+                pos += 2
+            values.append(value)
+        return values
+
+    xs = coordinates(X_SHORT, X_SAME_OR_POSITIVE)
+    ys = coordinates(Y_SHORT, Y_SAME_OR_POSITIVE)
+    return {
+        "ends": ends,
+        "on_curve": [flag & ON_CURVE for flag in flags],
+        "overlap": bool(flags and flags[0] & OVERLAP_SIMPLE),
+        "xs": xs,
+        "ys": ys,
+    }
+
+
+def parse_composite(data):
+    components = []
+    pos = 10
+    while True:
+        flags, glyph = struct.unpack(">HH", data[pos : pos + 4])
+        pos += 4
+        if flags & ARGS_ARE_WORDS:
+            arg1, arg2 = struct.unpack(">hh", data[pos : pos + 4])
+            pos += 4
+        else:
+            arg1, arg2 = struct.unpack(">bb", data[pos : pos + 2])
+            pos += 2
+        transform_size = (
+            2 if flags & HAVE_SCALE else 4 if flags & HAVE_X_AND_Y_SCALE else 8 if flags & HAVE_TWO_BY_TWO else 0
+        )
+        transform = data[pos : pos + transform_size] if transform_size else None
+        pos += transform_size
+        components.append({"flags": flags, "glyph": glyph, "args": (arg1, arg2), "transform": transform})
+        if not flags & MORE_COMPONENTS:
+            break
+    return {"components": components, "bbox": struct.unpack(">hhhh", data[2:10])}
+
+
+def signed_area(xs, ys, ends):
+    area = 0.0
+    start = 0
+    for end in ends:
+        for i in range(start, end + 1):
+            j = start if i == end else i + 1
+            area += xs[i] * ys[j] - xs[j] * ys[i]
+        start = end + 1
+    return area
+
+
+def embolden_contour(points, strength_x, strength_y, orientation):
+    """Move each point along the bisector of its edges, away from the ink."""
+    count = len(points)
+    moved = []
+    for i, (x, y) in enumerate(points):
+        previous = (i - 1) % count
+        while previous != i and points[previous] == points[i]:
+            previous = (previous - 1) % count
+        following = (i + 1) % count
+        while following != i and points[following] == points[i]:
+            following = (following + 1) % count
+        if previous == i or following == i:
+            moved.append((x, y))
+            continue
+
+        in_x, in_y = x - points[previous][0], y - points[previous][1]
+        length = math.hypot(in_x, in_y)
+        in_x, in_y = in_x / length, in_y / length
+        out_x, out_y = points[following][0] - x, points[following][1] - y
+        length = math.hypot(out_x, out_y)
+        out_x, out_y = out_x / length, out_y / length
+
+        # Left normals point away from the ink of clockwise outer contours
+        normal_in = (-in_y * orientation, in_x * orientation)
+        normal_out = (-out_y * orientation, out_x * orientation)
+        miter_x, miter_y = normal_in[0] + normal_out[0], normal_in[1] + normal_out[1]
+        cos = 1 + normal_in[0] * normal_out[0] + normal_in[1] * normal_out[1]
+        if cos < 0.25:
+            # Sharp corner, limit the miter
+            length = math.hypot(miter_x, miter_y) or 1.0
+            miter_x, miter_y = 2 * miter_x / length, 2 * miter_y / length
+        else:
+            miter_x, miter_y = miter_x / cos, miter_y / cos
+        moved.append((x + miter_x * strength_x, y + miter_y * strength_y))
+    return moved
+
+
+def encode_simple(glyph, xs, ys):
+    contours = len(glyph["ends"])
+    bbox = (min(xs), min(ys), max(xs), max(ys)) if xs else (0, 0, 0, 0)
+    data = struct.pack(">hhhhh", contours, *bbox)
+    data += struct.pack(f">{contours}H", *glyph["ends"])
+    data += struct.pack(">H", 0)  # no instructions
+
+    flags = []
+    x_data = b""
+    y_data = b""
+    previous_x = previous_y = 0
+    for i, (x, y) in enumerate(zip(xs, ys)):
+        flag = glyph["on_curve"][i]
+        if i == 0 and glyph["overlap"]:
+            flag |= OVERLAP_SIMPLE
+        dx, dy = x - previous_x, y - previous_y
+        previous_x, previous_y = x, y
+        if dx == 0:
+            flag |= X_SAME_OR_POSITIVE
+        elif -255 <= dx <= 255:
+            flag |= X_SHORT | (X_SAME_OR_POSITIVE if dx > 0 else 0)
+            x_data += bytes([abs(dx)])
+        else:
+            x_data += struct.pack(">h", dx)
+        if dy == 0:
+            flag |= Y_SAME_OR_POSITIVE
+        elif -255 <= dy <= 255:
+            flag |= Y_SHORT | (Y_SAME_OR_POSITIVE if dy > 0 else 0)
+            y_data += bytes([abs(dy)])
+        else:
+            y_data += struct.pack(">h", dy)
+        flags.append(flag)
+
+    flag_data = b""
+    i = 0
+    while i < len(flags):
+        repeat = 0
+        while i + repeat + 1 < len(flags) and flags[i + repeat + 1] == flags[i] and repeat < 255:
+            repeat += 1
+        if repeat > 1:
+            flag_data += bytes([flags[i] | REPEAT, repeat])
+            i += repeat + 1
+        else:
+            flag_data += bytes([flags[i]])
+            i += 1
+    return data + flag_data + x_data + y_data, bbox
+
+
+def encode_composite(glyph, bbox, scale_y):
+    data = struct.pack(">hhhhh", -1, *bbox)
+    for component in glyph["components"]:
+        flags = component["flags"] & ~HAVE_INSTRUCTIONS
+        arg1, arg2 = component["args"]
+        if flags & ARGS_ARE_XY_VALUES:
+            arg2 = round(arg2 * scale_y)
+            if not -128 <= arg2 <= 127:
+                flags |= ARGS_ARE_WORDS
+        data += struct.pack(">HH", flags, component["glyph"])
+        data += struct.pack(">hh" if flags & ARGS_ARE_WORDS else ">bb", arg1, arg2)
+        if component["transform"] is not None:
+            data += component["transform"]
+    return data
+
+
+def embolden(source, destination, strength_x, strength_y, weight, bold, names):
+    tables = read_tables(source)
+    glyphs = [parse_glyph(data) for data in read_glyphs(tables)]
+    count = len(glyphs)
+
+    (metrics,) = struct.unpack(">H", tables["hhea"][34:36])
+    advances = [struct.unpack(">H", tables["hmtx"][4 * i : 4 * i + 2])[0] for i in range(metrics)]
+    advances += [advances[-1]] * (count - metrics)
+
+    # Points on the baseline move down by strength_y, and the ones on the cap height up, so the
+    # outlines are scaled back vertically to keep both
+    (cap_height,) = struct.unpack(">h", tables["OS/2"][88:90])
+    scale_y = cap_height / (cap_height + 2 * strength_y)
+
+    encoded = [b""] * count
+    bboxes = [None] * count
+    for i, glyph in enumerate(glyphs):
+        if glyph is None or "components" in glyph:
+            continue
+        xs, ys = glyph["xs"], glyph["ys"]
+        orientation = -1 if signed_area(xs, ys, glyph["ends"]) > 0 else 1
+        # Marks without advance are drawn over the previous glyph, whose center moves right
+        shift_x = strength_x if advances[i] > 0 else -strength_x
+        new_xs, new_ys = [], []
+        start = 0
+        for end in glyph["ends"]:
+            contour = list(zip(xs[start : end + 1], ys[start : end + 1]))
+            for x, y in embolden_contour(contour, strength_x, strength_y, orientation):
+                new_xs.append(round(x + shift_x))
+                new_ys.append(round((y + strength_y) * scale_y))
+            start = end + 1
+        encoded[i], bboxes[i] = encode_simple(glyph, new_xs, new_ys)
+
+    def composite_bbox(i):
+        if bboxes[i] is None:
+            bbox = None
+            for component in glyphs[i]["components"]:
+                inner = composite_bbox(component["glyph"]) if glyphs[component["glyph"]] else None
+                if inner is None:
+                    continue
+                if component["transform"] is not None:
+                    # Scaled components keep the original bounds
+                    bbox = glyphs[i]["bbox"]
+                    break
+                dx, dy = 0, 0
+                if component["flags"] & ARGS_ARE_XY_VALUES:
+                    dx, dy = component["args"][0], round(component["args"][1] * scale_y)
+                inner = (inner[0] + dx, inner[1] + dy, inner[2] + dx, inner[3] + dy)
+                bbox = inner if bbox is None else (*map(min, bbox[:2], inner[:2]), *map(max, bbox[2:], inner[2:]))
+            bboxes[i] = bbox or (0, 0, 0, 0)
+        return bboxes[i]
+
+    for i, glyph in enumerate(glyphs):
+        if glyph is not None and "components" in glyph:
+            encoded[i] = encode_composite(glyph, composite_bbox(i), scale_y)
+
+    glyf = b""
+    offsets = []
+    for data in encoded:
+        offsets.append(len(glyf))
+        glyf += pad(data)
+    offsets.append(len(glyf))
+    tables["glyf"] = glyf
+    tables["loca"] = struct.pack(f">{count + 1}I", *offsets)
+
+    advances = [advance + 2 * strength_x if advance > 0 else 0 for advance in advances]
+    left_bearings = [bbox[0] if bbox else 0 for bbox in bboxes]
+    tables["hmtx"] = b"".join(struct.pack(">Hh", a, b) for a, b in zip(advances, left_bearings))
+
+    inked = [i for i in range(count) if bboxes[i] and bboxes[i] != (0, 0, 0, 0)]
+    hhea = bytearray(tables["hhea"])
+    struct.pack_into(">Hhhh", hhea, 10,
+                     max(advances),
+                     min(bboxes[i][0] for i in inked),
+                     min(advances[i] - bboxes[i][2] for i in inked),
+                     max(bboxes[i][2] for i in inked))
+    struct.pack_into(">H", hhea, 34, count)
+    tables["hhea"] = bytes(hhea)
+
+    head = bytearray(tables["head"])
+    struct.pack_into(">I", head, 8, 0)  # checksum adjustment, computed when writing
+    struct.pack_into(">hhhh", head, 36,
+                     min(bboxes[i][0] for i in inked),
+                     min(bboxes[i][1] for i in inked),
+                     max(bboxes[i][2] for i in inked),
+                     max(bboxes[i][3] for i in inked))
+    (mac_style,) = struct.unpack(">H", head[44:46])
+    struct.pack_into(">H", head, 44, mac_style | (0x1 if bold else 0))
+    struct.pack_into(">h", head, 50, 1)  # long loca offsets
+    tables["head"] = bytes(head)
+
+    os2 = bytearray(tables["OS/2"])
+    struct.pack_into(">H", os2, 4, weight)
+    (selection,) = struct.unpack(">H", os2[62:64])
+    selection &= ~0x40  # REGULAR
+    if bold:
+        selection |= 0x20  # BOLD
+    struct.pack_into(">H", os2, 62, selection)
+    tables["OS/2"] = bytes(os2)
+
+    # The hinting programs were written for the original outlines
+    for tag in ("fpgm", "prep", "cvt ", "gasp"):
+        tables.pop(tag, None)
+    tables["name"] = rename(tables["name"], names)
+    write_font(destination, tables)
+
+
+def rename(table, names):
+    _, count, strings = struct.unpack(">HHH", table[:6])
+    records = []
+    for i in range(count):
+        platform, encoding, language, name, length, offset = struct.unpack(">HHHHHH", table[6 + 12 * i : 18 + 12 * i])
+        value = table[strings + offset : strings + offset + length]
+        if name in names:
+            value = names[name].encode("utf-16-be" if platform in (0, 3) else "mac_roman")
+        records.append((platform, encoding, language, name, value))
+    windows_names = {record[3] for record in records if record[0] == 3}
+    for name, value in names.items():
+        if name not in windows_names:
+            records.append((3, 1, 0x409, name, value.encode("utf-16-be")))
+    records.sort(key=lambda record: record[:4])
+
+    header = struct.pack(">HHH", 0, len(records), 6 + 12 * len(records))
+    data = b""
+    for platform, encoding, language, name, value in records:
+        header += struct.pack(">HHHHHH", platform, encoding, language, name, len(value), len(data))
+        data += value
+    return header + data
+
+
+def pad(data):
+    return data + b"\0" * (-len(data) % 4)
+
+
+def checksum(data):
+    data = pad(data)
+    return sum(struct.unpack(f">{len(data) // 4}I", data)) & 0xFFFFFFFF
+
+
+def write_font(path, tables):
+    tags = sorted(tables)
+    count = len(tags)
+    search_range = 1 << (count.bit_length() - 1)
+    header = struct.pack(">IHHHH", 0x00010000, count, search_range * 16,
+                         search_range.bit_length() - 1, (count - search_range) * 16)
+    offset = 12 + 16 * count
+    directory = b""
+    body = b""
+    head_offset = 0
+    for tag in tags:
+        data = tables[tag]
+        if tag == "head":
+            head_offset = offset + len(body)
+        directory += struct.pack(">4sIII", tag.encode(), checksum(data), offset + len(body), len(data))
+        body += pad(data)
+    font = bytearray(header + directory + body)
+    struct.pack_into(">I", font, head_offset + 8, (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF)
+    open(path, "wb").write(bytes(font))
+
+
+def names(family, subfamily, full_name, typographic_subfamily):
+    postscript_name = full_name.replace(" ", "-", 1).replace(" ", "")
+    return {
+        1: family,
+        2: subfamily,
+        3: f"4.000;synthetic;{postscript_name}",
+        4: full_name,
+        6: postscript_name,
+        16: "Inter",
+        17: typographic_subfamily,
+    }
+
+
+if __name__ == "__main__":
+    # The stems of Inter Medium are 40 units wider than Regular, and its bars 28 units higher
+    embolden(FONTS_DIR + "Inter-Medium.ttf", FONTS_DIR + "Inter-SemiBold.ttf", 20, 14, 600, False,
+             names("Inter SemiBold", "Regular", "Inter SemiBold", "SemiBold"))
+    embolden(FONTS_DIR + "Inter-Medium.ttf", FONTS_DIR + "Inter-Bold.ttf", 40, 28, 700, True,
+             names("Inter", "Bold", "Inter Bold", "Bold"))
+    embolden(FONTS_DIR + "Inter-Italic.ttf", FONTS_DIR + "Inter-BoldItalic.ttf", 60, 42, 700, True,
+             names("Inter", "Bold Italic", "Inter Bold Italic", "Bold Italic"))